@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::solver::Answer;
+
+/// The known answers for a set of inputs, read from a manifest file like `data/answers.txt`:
+///
+/// ```text
+/// # input   part  answer
+/// data7.txt 1     169
+/// data7.txt 2     82372
+/// ```
+///
+/// Input files are relative to the manifest's directory, so a manifest travels with its inputs.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    answers: HashMap<(PathBuf, u32), String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Manifest, String> {
+        let text = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Manifest::parse(&text, dir).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn parse(text: &str, dir: &Path) -> Result<Manifest, String> {
+        let mut answers = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let (file, part, answer) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(file), Some(part), Some(answer)) => (file, part, answer.trim()),
+                _ => return Err(format!("line {}: expected <input> <part> <answer>", i + 1)),
+            };
+            let part = match part.parse() {
+                Ok(p @ 1..=2) => p,
+                _ => return Err(format!("line {}: part should be 1 or 2, not {}", i + 1, part)),
+            };
+            answers.insert((normalise(&dir.join(file)), part), answer.to_string());
+        }
+        Ok(Manifest { answers })
+    }
+
+    /// The expected answer for one part of an input, if we know it.
+    pub fn expected(&self, input: &Path, part: u32) -> Option<&str> {
+        self.answers.get(&(normalise(input), part)).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+/// Paths are compared once resolved, so `data/data7.txt` and `./data/data7.txt` are the same input.
+fn normalise(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.components().filter(|c| *c != Component::CurDir).collect())
+}
+
+/// How an answer compares with the manifest.
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unknown,
+}
+
+impl Check {
+    pub fn new(expected: Option<&str>, actual: &Answer) -> Check {
+        let actual = actual.to_string();
+        match expected {
+            None => Check::Unknown,
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_string(),
+                actual,
+            },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail { .. } => "fail",
+            Check::Unknown => "unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest() {
+        let text = "# Bob's answers\n\ndata7.txt 1 169\n./data7.txt 2 82372\nday21.txt 2 a,b c\n";
+        let manifest = Manifest::parse(text, Path::new("inputs/bob")).unwrap();
+        assert_eq!(3, manifest.len());
+        assert_eq!(Some("169"), manifest.expected(Path::new("inputs/bob/data7.txt"), 1));
+        assert_eq!(Some("82372"), manifest.expected(Path::new("./inputs/bob/./data7.txt"), 2));
+        assert_eq!(Some("a,b c"), manifest.expected(Path::new("inputs/bob/day21.txt"), 2));
+        assert_eq!(None, manifest.expected(Path::new("data/data7.txt"), 1));
+
+        assert!(Manifest::parse("data7.txt 1", Path::new("")).is_err());
+        assert!(Manifest::parse("data7.txt 3 169", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Check::Pass, Check::new(Some("169"), &Answer::from(169)));
+        assert_eq!(Check::Unknown, Check::new(None, &Answer::from(169)));
+        assert_eq!(
+            Check::Fail {
+                expected: String::from("170"),
+                actual: String::from("169")
+            },
+            Check::new(Some("170"), &Answer::from(169))
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+/// Timings for each phase of a day over a number of runs.
+#[derive(Debug, Default)]
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// A summary of one phase's timings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises a set of timings, or `None` if there aren't any.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        let total: Duration = sorted.iter().sum();
+
+        // Nearest-rank percentile, so p95 is always one of the actual timings.
+        let rank = |pct: usize| sorted[(runs * pct).div_ceil(100).max(1) - 1];
+
+        Some(Stats {
+            runs,
+            min: sorted[0],
+            median: rank(50),
+            mean: total / runs as u32,
+            p95: rank(95),
+        })
+    }
+}
+
+/// Formats a duration at a sensible scale for a table, e.g. `512us` or `3.141ms`.
+pub fn fmt_micros(d: Duration) -> String {
+    let us = d.as_micros();
+    if us < 1000 {
+        format!("{}us", us)
+    } else if us < 1_000_000 {
+        format!("{}.{:03}ms", us / 1000, us % 1000)
+    } else {
+        format!("{}.{:03}s", us / 1_000_000, (us / 1000) % 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn us(n: u64) -> Duration {
+        Duration::from_micros(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(None, Stats::from_samples(&[]));
+
+        let samples: Vec<Duration> = (1..=20).rev().map(us).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(20, stats.runs);
+        assert_eq!(us(1), stats.min);
+        assert_eq!(us(10), stats.median);
+        assert_eq!(Duration::from_nanos(10_500), stats.mean);
+        assert_eq!(us(19), stats.p95);
+
+        let stats = Stats::from_samples(&[us(7)]).unwrap();
+        assert_eq!((us(7), us(7), us(7)), (stats.min, stats.median, stats.p95));
+    }
+
+    #[test]
+    fn test_fmt_micros() {
+        assert_eq!("512us", fmt_micros(us(512)));
+        assert_eq!("3.041ms", fmt_micros(us(3041)));
+        assert_eq!("2.500s", fmt_micros(us(2_500_123)));
+    }
+}
@@ -0,0 +1,140 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Cancel>> = const { RefCell::new(None) };
+}
+
+/// Why a day stopped before it finished.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stopped {
+    /// It ran past its time budget.
+    TimedOut(Duration),
+    /// Somebody asked it to stop, e.g. from the dashboard.
+    Cancelled,
+}
+
+impl fmt::Display for Stopped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stopped::TimedOut(budget) => write!(f, "timed out after {:.1}s", budget.as_secs_f64()),
+            Stopped::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// A cancellation token for one run of a day, tripped either by its time budget running out or by calling `cancel`.
+///
+/// Days can't be stopped from outside, so the long loops call [`check`] now and then to see whether they should give
+/// up. Clones share the same token, so one can be kept to cancel a run happening on another thread.
+#[derive(Debug, Clone)]
+pub struct Cancel {
+    cancelled: Arc<AtomicBool>,
+    budget: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl Cancel {
+    /// A token with `budget` to spend from now, or no limit for `None`.
+    pub fn new(budget: Option<Duration>) -> Self {
+        Cancel {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            budget,
+            deadline: budget.map(|b| Instant::now() + b),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the run should stop, and why.
+    pub fn stopped(&self) -> Option<Stopped> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            Some(Stopped::Cancelled)
+        } else {
+            match (self.budget, self.deadline) {
+                (Some(budget), Some(deadline)) if Instant::now() >= deadline => Some(Stopped::TimedOut(budget)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Runs `f` on this thread under this token, so that any [`check`] it makes can stop it.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> Result<T, Stopped> {
+        let previous = CURRENT.with(|c| c.replace(Some(self.clone())));
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        CURRENT.with(|c| c.replace(previous));
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(payload) => match payload.downcast::<Stopped>() {
+                Ok(stopped) => Err(*stopped),
+                // Anything else is a real panic, and none of our business.
+                Err(payload) => panic::resume_unwind(payload),
+            },
+        }
+    }
+}
+
+/// Gives up on the current run if its token has been tripped, unwinding back to [`Cancel::run`]. Does nothing outside a
+/// run.
+///
+/// This looks at the clock, so in a tight loop it's best called every few thousand iterations rather than every one.
+pub fn check() {
+    if let Some(stopped) = CURRENT.with(|c| c.borrow().as_ref().and_then(Cancel::stopped)) {
+        // Unlike `panic!`, this skips the panic hook, so nothing gets printed on the way out.
+        panic::resume_unwind(Box::new(stopped) as Box<dyn Any + Send>);
+    }
+}
+
+/// What a panic said, from the payload handed back by `catch_unwind` or a thread's `join`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spin() -> u32 {
+        let mut n = 0_u32;
+        loop {
+            check();
+            n = n.wrapping_add(1);
+        }
+    }
+
+    #[test]
+    fn test_cancel() {
+        assert_eq!(Ok(3), Cancel::new(None).run(|| 1 + 2));
+
+        let budget = Duration::from_millis(20);
+        assert_eq!(Err(Stopped::TimedOut(budget)), Cancel::new(Some(budget)).run(spin));
+
+        let token = Cancel::new(None);
+        token.cancel();
+        assert_eq!(Err(Stopped::Cancelled), token.run(spin));
+        assert_eq!("timed out after 1.5s", Stopped::TimedOut(Duration::from_millis(1500)).to_string());
+
+        // Outside a run, checking is harmless.
+        check();
+    }
+
+    #[test]
+    fn test_panics_pass_through() {
+        let result = panic::catch_unwind(|| Cancel::new(None).run(|| panic!("real problem")));
+        assert_eq!("real problem", panic_message(&*result.unwrap_err()));
+        let result = panic::catch_unwind(|| Cancel::new(None).run(|| panic!("Tile {} doesn't fit!", 7)));
+        assert_eq!("Tile 7 doesn't fit!", panic_message(&*result.unwrap_err()));
+    }
+}
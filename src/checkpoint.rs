@@ -0,0 +1,204 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::warn;
+
+/// The start of every snapshot file, so anything else is turned away rather than read as nonsense.
+const MAGIC: &[u8; 8] = b"AOC20CK\x01";
+/// How often a long loop saves where it's got to.
+const SAVE_EVERY: Duration = Duration::from_secs(10);
+
+/// Where a long loop had got to, e.g. Day 15's record of when each number was last said, written out as a compact
+/// binary file that a later run can carry on from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub day: u32,
+    /// Tells runs of the same day apart, e.g. Day 15's part 1 from its part 2, or one input from another. Each loop makes
+    /// its own from whatever decides its answer.
+    pub key: u64,
+    /// How many iterations are done.
+    pub done: u64,
+    /// Anything the loop needs besides `state`, e.g. the last number said.
+    pub extra: u64,
+    pub state: Vec<u32>,
+}
+
+impl Snapshot {
+    pub fn load(path: &Path) -> Result<Snapshot, String> {
+        let file = File::open(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        Snapshot::read(&mut BufReader::new(file)).map_err(|err| format!("{} isn't a usable snapshot: {}", path.display(), err))
+    }
+
+    fn read(r: &mut impl Read) -> io::Result<Snapshot> {
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a snapshot"));
+        }
+        let day = read_u32(r)?;
+        let key = read_u64(r)?;
+        let done = read_u64(r)?;
+        let extra = read_u64(r)?;
+        let len = read_u64(r)?;
+        let mut bytes = vec![];
+        r.take(len.saturating_mul(4)).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len * 4 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "cut short"));
+        }
+        let state = bytes.chunks_exact(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+        Ok(Snapshot { day, key, done, extra, state })
+    }
+}
+
+/// Writes a snapshot without building one, since the state can be hundreds of megabytes.
+fn write(w: &mut impl Write, day: u32, key: u64, done: u64, extra: u64, state: &[u32]) -> io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_all(&day.to_le_bytes())?;
+    for n in [key, done, extra, state.len() as u64] {
+        w.write_all(&n.to_le_bytes())?;
+    }
+    for n in state {
+        w.write_all(&n.to_le_bytes())?;
+    }
+    w.flush()
+}
+
+fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    r.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(r: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    r.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+struct Settings {
+    save: Option<PathBuf>,
+    resume: Option<Snapshot>,
+}
+
+/// Where snapshots go and the one to carry on from, once `set`. Until then nothing is saved or resumed.
+static SETTINGS: Mutex<Settings> = Mutex::new(Settings { save: None, resume: None });
+
+/// Saves snapshots of long loops to `save` from now on, and has the first loop that `resume` is a snapshot of carry
+/// on from it.
+pub fn set(save: Option<PathBuf>, resume: Option<Snapshot>) {
+    *SETTINGS.lock().unwrap() = Settings { save, resume };
+}
+
+/// Whether the snapshot to resume from hasn't been used, e.g. because it was for another input.
+pub fn resume_pending() -> bool {
+    SETTINGS.lock().unwrap().resume.is_some()
+}
+
+/// The snapshots of one long loop: the one it starts from, if any, and saving its own every so often.
+///
+/// Loops check `due` alongside `cancel::check()`, and `save` when it says so, which costs next to nothing when nothing
+/// is being saved.
+pub struct Checkpoint {
+    day: u32,
+    key: u64,
+    save: Option<PathBuf>,
+    every: Duration,
+    saved: Instant,
+    resume: Option<Snapshot>,
+}
+
+impl Checkpoint {
+    pub fn new(day: u32, key: u64) -> Self {
+        let mut settings = SETTINGS.lock().unwrap();
+        let resume = match &settings.resume {
+            Some(snapshot) if snapshot.day == day && snapshot.key == key => settings.resume.take(),
+            _ => None,
+        };
+        Checkpoint::to(day, key, settings.save.clone(), SAVE_EVERY, resume)
+    }
+
+    fn to(day: u32, key: u64, save: Option<PathBuf>, every: Duration, resume: Option<Snapshot>) -> Self {
+        Checkpoint {
+            day,
+            key,
+            save,
+            every,
+            saved: Instant::now(),
+            resume,
+        }
+    }
+
+    /// The snapshot to carry on from, if there's one of this loop.
+    pub fn resume(&mut self) -> Option<Snapshot> {
+        self.resume.take()
+    }
+
+    /// Whether it's time to save another snapshot.
+    pub fn due(&self) -> bool {
+        self.save.is_some() && self.saved.elapsed() >= self.every
+    }
+
+    /// Saves where the loop has got to, replacing the last snapshot only once the new one is safely written. A snapshot
+    /// that can't be saved is warned about, but the loop carries on.
+    pub fn save(&mut self, done: u64, extra: u64, state: &[u32]) {
+        let Some(path) = &self.save else {
+            return;
+        };
+        let partial = path.with_extension("partial");
+        let written = File::create(&partial)
+            .and_then(|file| write(&mut BufWriter::new(file), self.day, self.key, done, extra, state))
+            .and_then(|()| fs::rename(&partial, path));
+        if let Err(err) = written {
+            warn!("Could not save a snapshot to {}: {}", path.display(), err);
+        }
+        self.saved = Instant::now();
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A checkpoint saving to a file of its own on every check, starting from `resume`, and a way to read back the
+    /// snapshot it last saved.
+    pub(crate) fn saving(day: u32, key: u64, name: &str, resume: Option<Snapshot>) -> (Checkpoint, impl Fn() -> Snapshot) {
+        let path = std::env::temp_dir().join(format!("advent2020-{}-{}.snapshot", name, std::process::id()));
+        let checkpoint = Checkpoint::to(day, key, Some(path.clone()), Duration::ZERO, resume);
+        (checkpoint, move || Snapshot::load(&path).unwrap())
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (mut checkpoint, saved) = saving(99, 0xabc, "round-trip", None);
+        assert!(checkpoint.due());
+        checkpoint.save(7, 3, &[1, u32::MAX, 0]);
+        let expected = Snapshot {
+            day: 99,
+            key: 0xabc,
+            done: 7,
+            extra: 3,
+            state: vec![1, u32::MAX, 0],
+        };
+        assert_eq!(expected, saved());
+    }
+
+    #[test]
+    fn test_not_a_snapshot() {
+        let mut bytes = vec![];
+        write(&mut bytes, 99, 1, 2, 3, &[4, 5]).unwrap();
+        assert!(Snapshot::read(&mut &bytes[..]).is_ok());
+        assert!(Snapshot::read(&mut &bytes[..bytes.len() - 1]).is_err());
+        assert!(Snapshot::read(&mut &b"99 1 2 3 4 5\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_nothing_saved_unless_set() {
+        // Nothing sets a place to save for day 99, whatever else the tests are doing.
+        let mut checkpoint = Checkpoint::new(99, 1);
+        assert!(!checkpoint.due());
+        assert_eq!(None, checkpoint.resume());
+    }
+}
@@ -0,0 +1,707 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::life::{Rule, Shape};
+use crate::log::Level;
+use crate::output::Format;
+use crate::params::{self, Params};
+use crate::solver::Parts;
+
+/// What the binary has been asked to do.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// List the known days.
+    List,
+    /// Run a single day, or every day for day 0.
+    Run(u32),
+    /// Run every day side by side and print a summary table.
+    All,
+    /// Time each phase of a day (or every day for 0) over many runs.
+    Bench(u32),
+    /// Check a day's answers (or every day's for 0) against the answers manifest.
+    Verify(u32),
+    /// Browse and run the days in a full-screen dashboard.
+    Tui,
+    /// Compare the latest benchmarks in the history against a baseline revision.
+    Compare,
+    /// Make up a random input for a day, written to the input file or stdout.
+    Generate(u32),
+    /// Set up a new day from the template: its module, registration and an empty input.
+    New(u32),
+    /// Run a day (or every day for 0) and write up the results as Markdown, to the file or stdout.
+    Report(u32),
+    /// Run a pattern of live cells as a Life-like automaton, with whatever rule and shape of world.
+    Life,
+}
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    /// `dataN.txt` in the data directory.
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub command: Command,
+    pub source: Source,
+    /// A directory of inputs to run a single day against one after another, instead of `source`.
+    pub inputs: Option<PathBuf>,
+    pub data_dir: PathBuf,
+    pub format: Format,
+    /// Which parts to run, verify or benchmark.
+    pub parts: Parts,
+    /// Timed runs of each phase when benchmarking.
+    pub runs: usize,
+    /// Untimed runs of each phase before benchmarking starts.
+    pub warmup: usize,
+    /// Worker threads for `all`, or `None` to pick from the number of CPUs.
+    pub jobs: Option<usize>,
+    /// The answers manifest to verify against, if not `answers.txt` in the data directory.
+    pub answers: Option<PathBuf>,
+    /// The puzzle parameters file, if not `params.txt` in the data directory.
+    pub params: Option<PathBuf>,
+    /// Parameters set on the command line, e.g. `15.part2_turns=1000000`, which win over the file.
+    pub param_overrides: Vec<String>,
+    /// The most detailed commentary to show from the days, or `None` for none at all.
+    pub log_level: Option<Level>,
+    /// How long each day may run before it's stopped, or `None` for as long as it takes.
+    pub timeout: Option<Duration>,
+    /// Budgets for particular days from `--timeout <day>=<secs>`, which win over `timeout`.
+    pub day_timeouts: Vec<(u32, Duration)>,
+    /// Where benchmark results are kept.
+    pub history: PathBuf,
+    /// The revision to compare against, if not the one benchmarked before the latest.
+    pub baseline: Option<String>,
+    /// How many percent slower a phase can get before `compare` calls it a regression.
+    pub threshold: f64,
+    /// The seed for `generate`, or `None` for a different input every time.
+    pub seed: Option<u64>,
+    /// How big an input `generate` makes, or `None` for the day's usual size.
+    pub scale: Option<usize>,
+    /// The puzzle title for `new`.
+    pub title: Option<String>,
+    /// Whether `report` includes the pictures some days can draw of their working.
+    pub visuals: bool,
+    /// Where long loops save snapshots of their progress, if not the `resume` file.
+    pub checkpoint: Option<PathBuf>,
+    /// A snapshot for the day to carry on from.
+    pub resume: Option<PathBuf>,
+    /// The birth and survival rule for `life`.
+    pub rule: Rule,
+    /// The shape of world `life` runs in.
+    pub shape: Shape,
+    /// How many generations `life` runs for.
+    pub generations: usize,
+}
+
+impl Config {
+    /// Reads the command line, e.g. `advent2020 7 my_input.txt` or `advent2020 0 --data-dir inputs/bob`.
+    ///
+    /// A filename of `-` reads the input from stdin, `--part 1` or `--part 2` runs just that part, and `--format json`
+    /// swaps the coloured text for JSON lines. `-v` and `-vv` show what the days have to say about their working, and
+    /// `-q` hides even their warnings. For `generate` and `report`, the file is where the output goes, with stdout for `-`
+    /// or no file at all, and for `life` it's the pattern to run.
+    pub fn new(args: impl IntoIterator<Item = String>) -> Result<Config, String> {
+        let mut args = args.into_iter().skip(1); // Skip the program name
+        let mut positional = vec![];
+        let mut data_dir = PathBuf::from("data");
+        let mut inputs = None;
+        let mut format = Format::Text;
+        let mut parts = Parts::Both;
+        let mut runs = 10;
+        let mut warmup = 2;
+        let mut jobs = None;
+        let mut answers = None;
+        let mut params = None;
+        let mut param_overrides = vec![];
+        let mut verbose = 0;
+        let mut quiet = false;
+        let mut timeout = None;
+        let mut day_timeouts = vec![];
+        let mut history = PathBuf::from("bench-history.csv");
+        let mut baseline = None;
+        let mut threshold = 10.0;
+        let mut seed = None;
+        let mut scale = None;
+        let mut title = None;
+        let mut visuals = false;
+        let mut checkpoint = None;
+        let mut resume = None;
+        let mut rule = None;
+        let mut shape = None;
+        let mut generations = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--data-dir" => match args.next() {
+                    Some(dir) => data_dir = PathBuf::from(dir),
+                    None => return Err(String::from("--data-dir needs a directory")),
+                },
+                "--inputs" => match args.next() {
+                    Some(dir) => inputs = Some(PathBuf::from(dir)),
+                    None => return Err(String::from("--inputs needs a directory")),
+                },
+                "--format" => match args.next().as_deref().map(Format::from_name) {
+                    Some(Some(f)) => format = f,
+                    _ => return Err(String::from("--format needs one of text or json")),
+                },
+                "--part" => match args.next().as_deref() {
+                    Some("1") => parts = Parts::One,
+                    Some("2") => parts = Parts::Two,
+                    _ => return Err(String::from("--part needs 1 or 2")),
+                },
+                "--runs" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => runs = n,
+                    _ => return Err(String::from("--runs needs a positive number")),
+                },
+                "--warmup" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) => warmup = n,
+                    None => return Err(String::from("--warmup needs a number")),
+                },
+                "--jobs" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => jobs = Some(n),
+                    _ => return Err(String::from("--jobs needs a positive number")),
+                },
+                "--answers" => match args.next() {
+                    Some(file) => answers = Some(PathBuf::from(file)),
+                    None => return Err(String::from("--answers needs a file")),
+                },
+                "--params" => match args.next() {
+                    Some(file) => params = Some(PathBuf::from(file)),
+                    None => return Err(String::from("--params needs a file")),
+                },
+                "--param" => match args.next() {
+                    Some(assignment) => {
+                        Params::default().apply(&assignment).map_err(|err| format!("--param {}", err))?;
+                        param_overrides.push(assignment);
+                    }
+                    None => return Err(String::from("--param needs <day>.<name>=<value>")),
+                },
+                "--timeout" => match args.next().as_deref().and_then(parse_timeout) {
+                    Some((None, secs)) => timeout = Some(secs),
+                    Some((Some(day), secs)) => day_timeouts.push((day, secs)),
+                    None => return Err(String::from("--timeout needs a positive number of seconds, or <day>=<seconds>")),
+                },
+                "--history" => match args.next() {
+                    Some(file) => history = PathBuf::from(file),
+                    None => return Err(String::from("--history needs a file")),
+                },
+                "--baseline" => match args.next() {
+                    Some(revision) => baseline = Some(revision),
+                    None => return Err(String::from("--baseline needs a revision")),
+                },
+                "--threshold" => match args.next().and_then(|n| n.parse::<f64>().ok()) {
+                    Some(pct) if pct >= 0.0 && pct.is_finite() => threshold = pct,
+                    _ => return Err(String::from("--threshold needs a percentage")),
+                },
+                "--seed" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) => seed = Some(n),
+                    None => return Err(String::from("--seed needs a number")),
+                },
+                "--scale" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => scale = Some(n),
+                    _ => return Err(String::from("--scale needs a positive number")),
+                },
+                "--title" => match args.next() {
+                    Some(t) => title = Some(t),
+                    None => return Err(String::from("--title needs a title")),
+                },
+                "--visuals" => visuals = true,
+                "--checkpoint" => match args.next() {
+                    Some(file) => checkpoint = Some(PathBuf::from(file)),
+                    None => return Err(String::from("--checkpoint needs a file")),
+                },
+                "--resume" => match args.next() {
+                    Some(file) => resume = Some(PathBuf::from(file)),
+                    None => return Err(String::from("--resume needs a file")),
+                },
+                "--rule" => match args.next() {
+                    Some(r) => rule = Some(r.parse::<Rule>().map_err(|err| format!("--rule {}", err))?),
+                    None => return Err(String::from("--rule needs a rule like B3/S23")),
+                },
+                "--topology" => match args.next().as_deref().map(Shape::from_name) {
+                    Some(Some(s)) => shape = Some(s),
+                    _ => return Err(format!("--topology needs one of {}", Shape::NAMES)),
+                },
+                "--generations" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) => generations = Some(n),
+                    None => return Err(String::from("--generations needs a number")),
+                },
+                "-v" | "--verbose" => verbose += 1,
+                "-vv" => verbose += 2,
+                "-q" | "--quiet" => quiet = true,
+                opt if opt.starts_with('-') && opt != "-" => return Err(format!("Unknown option {}", opt)),
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let command = match positional.next().as_deref() {
+            Some("list") => Command::List,
+            Some("all") => Command::All,
+            Some("tui") => Command::Tui,
+            Some("compare") => Command::Compare,
+            Some("bench") => Command::Bench(parse_day(positional.next())?),
+            Some("verify") => Command::Verify(parse_day(positional.next())?),
+            Some("generate") => match parse_day(positional.next())? {
+                0 => return Err(String::from("generate needs a single day")),
+                day => Command::Generate(day),
+            },
+            Some("report") => Command::Report(parse_day(positional.next())?),
+            Some("life") => Command::Life,
+            Some("new") => match parse_day(positional.next())? {
+                0 => return Err(String::from("new needs a single day")),
+                day => Command::New(day),
+            },
+            day => Command::Run(parse_day(day.map(String::from))?),
+        };
+
+        let source = match positional.next().as_deref() {
+            None => Source::Default,
+            Some("-") => Source::Stdin,
+            Some(filename) => Source::File(PathBuf::from(filename)),
+        };
+
+        if matches!(
+            command,
+            Command::Run(0) | Command::Bench(0) | Command::Verify(0) | Command::All | Command::Tui | Command::Compare | Command::New(_)
+        ) && source != Source::Default
+        {
+            return Err(String::from("An input file can only be given for a single day"));
+        }
+
+        if inputs.is_some() {
+            if !matches!(command, Command::Run(day) if day > 0) {
+                return Err(String::from("--inputs only works when running a single day"));
+            }
+            if source != Source::Default {
+                return Err(String::from("--inputs can't be used with an input file"));
+            }
+        }
+
+        if (checkpoint.is_some() || resume.is_some()) && (!matches!(command, Command::Run(day) if day > 0) || inputs.is_some())
+        {
+            return Err(String::from("--checkpoint and --resume only work when running a single day on one input"));
+        }
+
+        if command == Command::Life {
+            if source == Source::Default {
+                return Err(String::from("life needs a pattern file, or - for stdin"));
+            }
+            if rule.is_some_and(|rule: Rule| rule.born(0)) {
+                return Err(String::from("life can't run a B0 rule, as cells born from nothing fill the world"));
+            }
+        } else if rule.is_some() || shape.is_some() || generations.is_some() {
+            return Err(String::from("--rule, --topology and --generations only apply to life"));
+        }
+
+        if visuals && !matches!(command, Command::Report(_)) {
+            return Err(String::from("--visuals only applies to report"));
+        }
+
+        if parts != Parts::Both
+            && matches!(
+                command,
+                Command::Tui | Command::Compare | Command::Generate(_) | Command::New(_) | Command::Life
+            )
+        {
+            return Err(String::from("--part only applies to running days"));
+        }
+
+        if let Some(extra) = positional.next() {
+            return Err(format!("Unexpected argument {}", extra));
+        }
+
+        let log_level = match (quiet, verbose) {
+            (true, 0) => None,
+            (true, _) => return Err(String::from("-q and -v can't be used together")),
+            (false, 0) => Some(Level::Warn),
+            (false, 1) => Some(Level::Debug),
+            (false, _) => Some(Level::Trace),
+        };
+
+        Ok(Config {
+            command,
+            source,
+            inputs,
+            data_dir,
+            format,
+            parts,
+            runs,
+            warmup,
+            jobs,
+            answers,
+            params,
+            param_overrides,
+            log_level,
+            timeout,
+            day_timeouts,
+            history,
+            baseline,
+            threshold,
+            seed,
+            scale,
+            title,
+            visuals,
+            checkpoint,
+            resume,
+            rule: rule.unwrap_or(Rule::CONWAY),
+            shape: shape.unwrap_or(Shape::Square),
+            generations: generations.unwrap_or(100),
+        })
+    }
+
+    /// Where to save snapshots of long loops, if anywhere. Resuming keeps saving to the snapshot it started from,
+    /// unless told otherwise.
+    pub fn checkpoint_path(&self) -> Option<&Path> {
+        self.checkpoint.as_deref().or(self.resume.as_deref())
+    }
+
+    /// The file a day's input is read from, or `None` when reading stdin.
+    pub fn input_path(&self, day: u32) -> Option<PathBuf> {
+        match &self.source {
+            Source::Default => Some(self.data_dir.join(format!("data{}.txt", day))),
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin => None,
+        }
+    }
+
+    /// The files in the `--inputs` directory to run a day against, in name order. Where some are named for the day, like
+    /// `data16.txt` or `data16_small.txt`, it's just those, so one directory can hold the inputs for every day.
+    pub fn batch_inputs(&self, day: u32) -> io::Result<Vec<PathBuf>> {
+        let Some(dir) = &self.inputs else {
+            return Ok(vec![]);
+        };
+        let mut files = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let hidden = path.file_name().is_none_or(|name| name.to_string_lossy().starts_with('.'));
+            if path.is_file() && !hidden {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let prefix = format!("data{}", day);
+        let for_day = |path: &PathBuf| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.strip_prefix(&prefix).is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+        };
+        if files.iter().any(for_day) {
+            files.retain(for_day);
+        }
+        Ok(files)
+    }
+
+    /// A short description of where a day's input comes from, for messages.
+    pub fn input_name(&self, day: u32) -> String {
+        match self.input_path(day) {
+            Some(path) => path.display().to_string(),
+            None => String::from("<stdin>"),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.answers.clone().unwrap_or_else(|| self.data_dir.join("answers.txt"))
+    }
+
+    pub fn params_path(&self) -> PathBuf {
+        self.params.clone().unwrap_or_else(|| self.data_dir.join("params.txt"))
+    }
+
+    /// The puzzle parameters from the file with the command line's on top. The data directory doesn't need a
+    /// `params.txt`, but a file given with `--params` has to be there.
+    pub fn load_params(&self) -> Result<Params, String> {
+        let path = self.params_path();
+        let mut params = if self.params.is_none() && !path.exists() {
+            Params::default()
+        } else {
+            Params::load(&path)?
+        };
+        for assignment in &self.param_overrides {
+            params.apply(assignment)?;
+        }
+        Ok(params)
+    }
+
+    /// How long a day may run: its own budget from `--timeout <day>=<secs>` or its `timeout` parameter, if it has one,
+    /// or else the one for every day.
+    pub fn timeout_for(&self, day: u32) -> Option<Duration> {
+        let own = self.day_timeouts.iter().rev().find(|&&(d, _)| d == day).map(|&(_, timeout)| timeout);
+        own.or_else(|| params::timeout(day)).or(self.timeout)
+    }
+
+    pub fn read_input(&self, day: u32) -> io::Result<String> {
+        match self.input_path(day) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut data = String::new();
+                io::stdin().read_to_string(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+fn parse_day(arg: Option<String>) -> Result<u32, String> {
+    match arg {
+        Some(day) => day
+            .parse()
+            .map_err(|_| format!("No valid day on command line ({})", day)),
+        None => Err(String::from("No day given")),
+    }
+}
+
+/// Reads `<secs>` for every day, or `<day>=<secs>` for just one.
+fn parse_timeout(arg: &str) -> Option<(Option<u32>, Duration)> {
+    let (day, secs) = match arg.split_once('=') {
+        Some((day, secs)) => (Some(day.parse().ok()?), secs),
+        None => (None, arg),
+    };
+    params::seconds(secs).then(|| (day, Duration::from_secs_f64(secs.parse().unwrap())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(args: &str) -> Result<Config, String> {
+        Config::new(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_sources() {
+        let c = config("advent2020 7").unwrap();
+        assert_eq!(Command::Run(7), c.command);
+        assert_eq!(Some(PathBuf::from("data/data7.txt")), c.input_path(7));
+
+        let c = config("advent2020 7 --data-dir inputs/bob").unwrap();
+        assert_eq!(Some(PathBuf::from("inputs/bob/data7.txt")), c.input_path(7));
+
+        let c = config("advent2020 7 mine.txt").unwrap();
+        assert_eq!(Some(PathBuf::from("mine.txt")), c.input_path(7));
+
+        let c = config("advent2020 7 -").unwrap();
+        assert_eq!(None, c.input_path(7));
+        assert_eq!("<stdin>", c.input_name(7));
+        assert_eq!(Format::Text, c.format);
+
+        let c = config("advent2020 0 --format json").unwrap();
+        assert_eq!(Format::Json, c.format);
+    }
+
+    #[test]
+    fn test_inputs() {
+        let c = config("advent2020 16 --inputs data").unwrap();
+        assert_eq!(Some(PathBuf::from("data")), c.inputs);
+        assert_eq!(
+            vec!["data/data16.txt", "data/data16_small.txt", "data/data16_small2.txt"],
+            c.batch_inputs(16).unwrap().iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
+        );
+        // Nothing here is named for day 99, so it would be everything.
+        assert!(c.batch_inputs(99).unwrap().len() > 25);
+        assert!(config("advent2020 16 --inputs no-such-dir").unwrap().batch_inputs(16).is_err());
+
+        assert!(config("advent2020 0 --inputs data").is_err());
+        assert!(config("advent2020 all --inputs data").is_err());
+        assert!(config("advent2020 16 mine.txt --inputs data").is_err());
+        assert!(config("advent2020 16 --inputs").is_err());
+    }
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(Some(Level::Warn), config("advent2020 7").unwrap().log_level);
+        assert_eq!(Some(Level::Debug), config("advent2020 7 -v").unwrap().log_level);
+        assert_eq!(Some(Level::Trace), config("advent2020 -vv 7").unwrap().log_level);
+        assert_eq!(Some(Level::Trace), config("advent2020 all -v --verbose").unwrap().log_level);
+        assert_eq!(None, config("advent2020 7 -q").unwrap().log_level);
+        assert!(config("advent2020 7 -q -v").is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let c = config("advent2020 bench 7").unwrap();
+        assert_eq!(Command::Bench(7), c.command);
+        assert_eq!((10, 2), (c.runs, c.warmup));
+
+        let c = config("advent2020 bench 0 --runs 50 --warmup 5").unwrap();
+        assert_eq!(Command::Bench(0), c.command);
+        assert_eq!((50, 5), (c.runs, c.warmup));
+    }
+
+    #[test]
+    fn test_compare() {
+        let c = config("advent2020 compare").unwrap();
+        assert_eq!(Command::Compare, c.command);
+        assert_eq!((PathBuf::from("bench-history.csv"), None), (c.history, c.baseline));
+        assert!((c.threshold - 10.0).abs() < f64::EPSILON);
+
+        let c = config("advent2020 compare --baseline 3142aaa --threshold 5 --history h.csv").unwrap();
+        assert_eq!(Some(String::from("3142aaa")), c.baseline);
+        assert_eq!(PathBuf::from("h.csv"), c.history);
+        assert!((c.threshold - 5.0).abs() < f64::EPSILON);
+
+        assert!(config("advent2020 compare --threshold -1").is_err());
+        assert!(config("advent2020 compare data7.txt").is_err());
+    }
+
+    #[test]
+    fn test_all() {
+        let c = config("advent2020 all").unwrap();
+        assert_eq!(Command::All, c.command);
+        assert_eq!(None, c.jobs);
+        assert_eq!(Some(4), config("advent2020 all --jobs 4").unwrap().jobs);
+        assert_eq!(None, c.timeout);
+        assert_eq!(
+            Some(Duration::from_millis(2500)),
+            config("advent2020 all --timeout 2.5").unwrap().timeout
+        );
+    }
+
+    #[test]
+    fn test_day_timeouts() {
+        // A day's own budget wins over the one for every day, wherever it comes in the arguments.
+        let c = config("advent2020 all --timeout 23=30 --timeout 2.5 --timeout 15=0.5 --timeout 23=60").unwrap();
+        assert_eq!(Some(Duration::from_secs(60)), c.timeout_for(23));
+        assert_eq!(Some(Duration::from_millis(500)), c.timeout_for(15));
+        assert_eq!(Some(Duration::from_millis(2500)), c.timeout_for(7));
+        assert_eq!(None, config("advent2020 all --timeout 23=30").unwrap().timeout_for(7));
+        assert!(config("advent2020 all --timeout 23=0").is_err());
+        assert!(config("advent2020 all --timeout x=5").is_err());
+        assert!(config("advent2020 all --timeout 23=").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let c = config("advent2020 verify 7 --data-dir inputs/bob").unwrap();
+        assert_eq!(Command::Verify(7), c.command);
+        assert_eq!(PathBuf::from("inputs/bob/answers.txt"), c.answers_path());
+
+        let c = config("advent2020 verify 0 --answers mine.txt").unwrap();
+        assert_eq!(PathBuf::from("mine.txt"), c.answers_path());
+    }
+
+    #[test]
+    fn test_generate() {
+        let c = config("advent2020 generate 20").unwrap();
+        assert_eq!(Command::Generate(20), c.command);
+        assert_eq!((None, None, Source::Default), (c.seed, c.scale, c.source));
+
+        let c = config("advent2020 generate 20 big.txt --seed 7 --scale 14").unwrap();
+        assert_eq!((Some(7), Some(14)), (c.seed, c.scale));
+        assert_eq!(Source::File(PathBuf::from("big.txt")), c.source);
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(Parts::Both, config("advent2020 15").unwrap().parts);
+        assert_eq!(Parts::One, config("advent2020 15 --part 1").unwrap().parts);
+        assert_eq!(Parts::Two, config("advent2020 verify 0 --part 2").unwrap().parts);
+        assert!(config("advent2020 15 --part 3").is_err());
+        assert!(config("advent2020 15 --part").is_err());
+        assert!(config("advent2020 tui --part 1").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        let c = config("advent2020 15 --data-dir inputs/bob").unwrap();
+        assert_eq!(PathBuf::from("inputs/bob/params.txt"), c.params_path());
+        assert!(c.load_params().unwrap().is_empty());
+
+        let c = config("advent2020 15 --params mine.txt --param 15.part2_turns=1000 --param 23.cups=9").unwrap();
+        assert_eq!(PathBuf::from("mine.txt"), c.params_path());
+        assert_eq!(vec!["15.part2_turns=1000", "23.cups=9"], c.param_overrides);
+        assert!(c.load_params().is_err());
+
+        let c = config("advent2020 15 --data-dir src --param 15.part2_turns=1000").unwrap();
+        assert_eq!(Some("1000"), c.load_params().unwrap().value(15, "part2_turns"));
+    }
+
+    #[test]
+    fn test_report() {
+        let c = config("advent2020 report 0").unwrap();
+        assert_eq!((Command::Report(0), Source::Default, false), (c.command, c.source, c.visuals));
+        let c = config("advent2020 report 11 results.md --visuals --part 1").unwrap();
+        assert_eq!((Command::Report(11), Parts::One, true), (c.command, c.parts, c.visuals));
+        assert_eq!(Source::File(PathBuf::from("results.md")), c.source);
+        assert!(config("advent2020 11 --visuals").is_err());
+        assert!(config("advent2020 report").is_err());
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let c = config("advent2020 15 --checkpoint day15.snap").unwrap();
+        assert_eq!((None, Some(Path::new("day15.snap"))), (c.resume.as_deref(), c.checkpoint_path()));
+        let c = config("advent2020 15 --resume day15.snap").unwrap();
+        assert_eq!(Some(Path::new("day15.snap")), c.checkpoint_path());
+        let c = config("advent2020 23 mine.txt --resume old.snap --checkpoint new.snap").unwrap();
+        assert_eq!(Some(Path::new("new.snap")), c.checkpoint_path());
+        assert_eq!(None, config("advent2020 23").unwrap().checkpoint_path());
+        assert!(config("advent2020 0 --checkpoint day15.snap").is_err());
+        assert!(config("advent2020 bench 15 --resume day15.snap").is_err());
+        assert!(config("advent2020 15 --inputs data --checkpoint day15.snap").is_err());
+        assert!(config("advent2020 15 --resume").is_err());
+    }
+
+    #[test]
+    fn test_life() {
+        let c = config("advent2020 life glider.txt").unwrap();
+        assert_eq!((Command::Life, Rule::CONWAY, Shape::Square, 100), (c.command, c.rule, c.shape, c.generations));
+        assert_eq!(Source::File(PathBuf::from("glider.txt")), c.source);
+        let c = config("advent2020 life - --rule b2/s12 --topology hex --generations 10").unwrap();
+        assert_eq!((Source::Stdin, Shape::Hex, 10), (c.source, c.shape, c.generations));
+        assert_eq!(Rule::new(&[2], &[1, 2]), c.rule);
+        assert!(config("advent2020 life").is_err());
+        assert!(config("advent2020 life glider.txt --rule B36").is_err());
+        assert!(config("advent2020 life glider.txt --rule B0/S8").is_err());
+        assert!(config("advent2020 life glider.txt --topology torus").is_err());
+        assert!(config("advent2020 17 --topology 4d").is_err());
+    }
+
+    #[test]
+    fn test_new() {
+        let c = config("advent2020 new 26").unwrap();
+        assert_eq!((Command::New(26), None), (c.command, c.title));
+        let c = Config::new(vec!["advent2020", "new", "1", "--title", "Report Repair"].into_iter().map(String::from));
+        assert_eq!(Some(String::from("Report Repair")), c.unwrap().title);
+    }
+
+    #[test]
+    fn test_bad_args() {
+        assert!(config("advent2020").is_err());
+        assert!(config("advent2020 seven").is_err());
+        assert!(config("advent2020 0 mine.txt").is_err());
+        assert!(config("advent2020 7 --data-dir").is_err());
+        assert!(config("advent2020 7 --colour").is_err());
+        assert!(config("advent2020 7 -x").is_err());
+        assert!(config("advent2020 7 --format").is_err());
+        assert!(config("advent2020 7 --format xml").is_err());
+        assert!(config("advent2020 7 a.txt b.txt").is_err());
+        assert!(config("advent2020 bench").is_err());
+        assert!(config("advent2020 new 0").is_err());
+        assert!(config("advent2020 15 --params").is_err());
+        assert!(config("advent2020 15 --param").is_err());
+        assert!(config("advent2020 15 --param turns=1000").is_err());
+        assert!(config("advent2020 new 26 day26.txt").is_err());
+        assert!(config("advent2020 new 26 --title").is_err());
+        assert!(config("advent2020 bench 0 mine.txt").is_err());
+        assert!(config("advent2020 bench 7 --runs 0").is_err());
+        assert!(config("advent2020 all mine.txt").is_err());
+        assert!(config("advent2020 all --jobs 0").is_err());
+        assert!(config("advent2020 all --timeout 0").is_err());
+        assert!(config("advent2020 all --timeout soon").is_err());
+        assert!(config("advent2020 tui mine.txt").is_err());
+        assert!(config("advent2020 verify").is_err());
+        assert!(config("advent2020 verify 0 mine.txt").is_err());
+        assert!(config("advent2020 generate 0").is_err());
+        assert!(config("advent2020 generate 7 --seed x").is_err());
+        assert!(config("advent2020 generate 7 --scale 0").is_err());
+        assert_eq!(Command::List, config("advent2020 list").unwrap().command);
+        assert_eq!(Command::Tui, config("advent2020 tui").unwrap().command);
+    }
+}
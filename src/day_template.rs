@@ -1,26 +1,45 @@
-use std::time::SystemTime;
+use crate::{Answer, ParseError, Solver};
 
-use advent2020::{fmt_bright, print_day, print_duration};
+pub struct Day<DAY>;
 
-fn data() -> &'static str {
-    include_str!("../data/data<DAY>.txt")
-}
+impl Solver for Day<DAY> {
+    type Input = String;
+
+    fn id(&self) -> u32 {
+        <DAY>
+    }
 
-pub fn run() {
-    print_day();
+    fn title(&self) -> &'static str {
+        "<TITLE>"
+    }
 
-    let start = SystemTime::now();
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        Ok(data.to_string())
+    }
 
-    // Let's do this...
+    fn part1(&self, _input: &Self::Input) -> Answer {
+        // Let's do this...
+        0.into()
+    }
 
-    let timed = SystemTime::now().duration_since(start).unwrap();
-    print_duration(timed);
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        0.into()
+    }
 }
 
+#[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    fn data() -> &'static str {
+        include_str!("../../data/data<DAY>.txt")
+    }
+
     #[test]
-    fn test_all() {}
+    fn test_all() {
+        let input = Day<DAY>.parse(data()).unwrap();
+        assert_eq!(Answer::from(0), Day<DAY>.part1(&input));
+        assert_eq!(Answer::from(0), Day<DAY>.part2(&input));
+    }
 }
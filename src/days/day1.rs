@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use crate::params::Param;
+use crate::{params, Answer, ParseError, Rng, Solver};
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = HashSet<i32>;
+
+    fn id(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Report Repair"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "total", valid: params::positive::<i32> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        data.lines()
+            .enumerate()
+            .filter(|(_, x)| !x.trim().is_empty())
+            .map(|(i, x)| {
+                x.trim()
+                    .parse::<i32>()
+                    .map_err(|_| ParseError::at_line(1, data, i + 1, 1, "an expense (a number)"))
+            })
+            .collect()
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        sums_to(input, params::get(1, "total", 2020))
+            .first()
+            .map_or(0, |(a, b)| a * b)
+            .into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        triple_sums_to(input, params::get(1, "total", 2020))
+            .first()
+            .map_or(0, |(a, b, c)| a * b * c)
+            .into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // Plant one pair and one triple adding up to the total, hidden among other expenses.
+        let total = u64::try_from(params::get(1, "total", 2020_i32)).unwrap_or(0).max(4);
+        let a = rng.between(1, total - 1);
+        let x = rng.between(1, (total / 2).saturating_sub(10).max(1));
+        let y = rng.between(1, (total / 2).saturating_sub(10).max(1));
+        let mut expenses = vec![a, total - a, x, y, total - x - y];
+        while expenses.len() < scale.unwrap_or(200).max(5) {
+            expenses.push(rng.between(1, total - 1));
+        }
+        rng.shuffle(&mut expenses);
+        Some(expenses.iter().map(|e| e.to_string() + "\n").collect())
+    }
+}
+
+/// Every pair of different values adding up to `total`, smallest first.
+pub fn sums_to(vals: &HashSet<i32>, total: i32) -> Vec<(i32, i32)> {
+    let mut results: Vec<(i32, i32)> = Vec::new();
+    for val in vals {
+        let complement = total - val;
+        if val < &complement && vals.contains(&complement) {
+            results.push((*val, complement));
+        }
+    }
+    results
+}
+
+/// Every triple of different values adding up to `total`, smallest first.
+pub fn triple_sums_to(vals: &HashSet<i32>, total: i32) -> Vec<(i32, i32, i32)> {
+    let mut results: Vec<(i32, i32, i32)> = Vec::new();
+    for val in vals {
+        let complement = total - val;
+        if val < &complement {
+            let complement_sums = sums_to(vals, complement);
+            for (a, b) in complement_sums {
+                if *val < a {
+                    results.push((*val, a, b));
+                }
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_test() {
+        let data = vec![1721, 979, 366, 299, 675, 1456];
+        let data: HashSet<i32> = data.into_iter().collect();
+        let res = sums_to(&data, 2020);
+        assert_eq!(1, res.len());
+        assert_eq!((299, 1721), res[0]);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = vec![1721, 979, 366, 299, 675, 1456];
+        let data: HashSet<i32> = data.into_iter().collect();
+        let res = triple_sums_to(&data, 2020);
+        assert_eq!(1, res.len());
+        assert_eq!((366, 675, 979), res[0]);
+    }
+
+    #[test]
+    fn all_test() {
+        let input = Day1.parse(include_str!("../../data/data1.txt")).unwrap();
+        assert_eq!(Answer::Number(290_784), Day1.part1(&input));
+        assert_eq!(Answer::Number(177_337_980), Day1.part2(&input));
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::{Answer, ParseError, Rng, Solver};
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<usize>;
+
+    fn id(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Adapter Array"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_adapters(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        jump_product(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        chains(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // Jumps of 1 or 3 jolts, with no more than four 1s in a row. Each run of 1s multiplies the number of chains, so
+        // once there are enough chains to be going on with, the jumps are all 3s to keep the count from overflowing.
+        const CHAINS: [u64; 5] = [1, 1, 2, 4, 7];
+        let mut chains = 1_u64;
+        let mut run = 0;
+        let mut jolts = 0;
+        let mut adapters = vec![];
+        for _ in 0..scale.unwrap_or(100) {
+            let grown = chains / CHAINS[run] * CHAINS[(run + 1).min(4)];
+            if run < 4 && grown < 1_000_000_000_000_000 && rng.chance(0.7) {
+                chains = grown;
+                run += 1;
+                jolts += 1;
+            } else {
+                run = 0;
+                jolts += 3;
+            }
+            adapters.push(jolts);
+        }
+        rng.shuffle(&mut adapters);
+        Some(adapters.iter().map(|a| a.to_string() + "\n").collect())
+    }
+}
+
+/// The adapters' joltages, smallest first.
+pub fn parse_adapters(data: &str) -> Result<Vec<usize>, ParseError> {
+    let mut adapters = data
+        .lines()
+        .enumerate()
+        .filter(|(_, x)| !x.trim().is_empty())
+        .map(|(i, x)| x.trim().parse().map_err(|_| ParseError::at_line(10, data, i + 1, 1, "a joltage")))
+        .collect::<Result<Vec<usize>, _>>()?;
+    adapters.sort_unstable();
+    Ok(adapters)
+}
+
+/// The device's joltage, 3 above the biggest adapter.
+pub fn target(adapters: &[usize]) -> usize {
+    adapters.last().map_or(0, |a| a + 3)
+}
+
+/// The number of 1-jolt jumps times the number of 3-jolt jumps, using every (sorted) adapter.
+pub fn jump_product(adapters: &[usize]) -> usize {
+    let mut single_jumps = 0;
+    let mut triple_jumps = 1; // Always get one for the jump to my device.
+    let mut jolts = 0; // The zero powered socket.
+    for adapter in adapters {
+        if adapter - jolts == 1 {
+            single_jumps += 1;
+        } else if adapter - jolts == 3 {
+            triple_jumps += 1;
+        }
+        jolts = *adapter;
+    }
+    single_jumps * triple_jumps
+}
+
+/// How many different chains of (sorted) adapters reach the device.
+pub fn chains(adapters: &[usize]) -> usize {
+    let mut memo = HashMap::new();
+    valid_chains(adapters, 0, 0, target(adapters), &mut memo)
+}
+
+fn valid_chains(adapters: &[usize], idx: usize, curr: usize, target: usize, memo: &mut HashMap<(usize, usize), usize>) -> usize {
+    // We count the valid chains from here.
+    if let Some(val) = memo.get(&(idx, curr)) {
+        return *val;
+    }
+
+    let mut valid = 0;
+    if (target - curr) <= 3 {
+        // We already there! This is a valid target.
+        valid += 1;
+    }
+
+    // If the next value is within 3, try that.
+    if let Some(jolt) = adapters.get(idx) {
+        // If within 3, we can use it.
+        if (jolt - curr) <= 3 {
+            valid += valid_chains(adapters, idx + 1, *jolt, target, memo);
+            // Also, try skipping it
+            valid += valid_chains(adapters, idx + 1, curr, target, memo);
+        }
+    }
+    memo.insert((idx, curr), valid);
+    
+    // Note that if we're out of index, or the gap is too big, we don't recurse.
+    valid
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data10.txt")
+    }
+
+    fn report(data: &str) -> (usize, usize, usize) {
+        let adapters = parse_adapters(data).unwrap();
+        (jump_product(&adapters), target(&adapters), chains(&adapters))
+    }
+
+    #[test]
+    fn test_small() {
+        let small_data = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+        assert_eq!((35, 22, 8), report(small_data));
+    }
+        
+    #[test]
+    fn test_med() {
+        let med_data = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+        assert_eq!((220, 52, 19208), report(med_data));
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!((1917, 152, 113_387_824_750_592), report(data()));
+    }
+}
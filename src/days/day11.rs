@@ -0,0 +1,251 @@
+use std::convert::{TryFrom, TryInto};
+
+use crate::grid::Cell;
+use crate::life::{Automaton, Dense, Fixed, Rule};
+use crate::progress::Progress;
+use crate::{cancel, warn, Answer, Grid2D, ParseError, Rng, Solver};
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input = Grid2D<Tile>;
+
+    fn id(&self) -> u32 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Seating System"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        Grid2D::parse(11, data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        run_day(input, &NeighbourMode::Adjacent).occupied.into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        run_day(input, &NeighbourMode::Sight).occupied.into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // A plan `scale` rows deep and a little wider, like the real one. Plenty of them never settle, so keep trying
+        // until one does.
+        let rows = scale.unwrap_or(90);
+        let cols = rows + rows / 10;
+        let mut plan = String::new();
+        for _ in 0..20 {
+            plan.clear();
+            for _ in 0..rows {
+                plan.extend((0..cols).map(|_| if rng.chance(0.25) { '.' } else { 'L' }));
+                plan.push('\n');
+            }
+            let limit = 4 * (rows + cols);
+            let seats = Grid2D::parse(11, &plan).unwrap();
+            if settle(&seats, &NeighbourMode::Adjacent, limit).is_some()
+                && settle(&seats, &NeighbourMode::Sight, limit).is_some()
+            {
+                return Some(plan);
+            }
+        }
+        warn!("Couldn't find a {}x{} plan that settles, so this one won't.", rows, cols);
+        Some(plan)
+    }
+
+    fn reference(&self, input: &Self::Input, part: u32) -> Option<Answer> {
+        let mode = if part == 1 { NeighbourMode::Adjacent } else { NeighbourMode::Sight };
+        Some(settle_naive(input, &mode).into())
+    }
+
+    fn visualise(&self, input: &Self::Input) -> Option<String> {
+        Some(settled_map(input, &NeighbourMode::Adjacent))
+    }
+}
+
+/// Which seats count as neighbours.
+pub enum NeighbourMode {
+    /// The eight seats around (a tolerance of 4).
+    Adjacent,
+    /// The first seat visible in each of the eight directions (a tolerance of 5).
+    Sight,
+}
+
+/// The seating once it stops changing.
+#[derive(Debug, PartialEq)]
+pub struct Settled {
+    pub rounds: i32,
+    pub occupied: i32,
+}
+
+/// One spot on the seat layout: `.`, `L` or `#`.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Tile {
+    Floor,
+    EmptySeat,
+    FullSeat,
+}
+
+impl Cell for Tile {
+    const EXPECTED: &'static str = "floor (.) or a seat (L or #)";
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Tile::Floor),
+            'L' => Some(Tile::EmptySeat),
+            '#' => Some(Tile::FullSeat),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Tile::Floor => '.',
+            Tile::EmptySeat => 'L',
+            Tile::FullSeat => '#',
+        }
+    }
+}
+
+pub fn run_day(plan: &Grid2D<Tile>, mode: &NeighbourMode) -> Settled {
+    settle(plan, mode, usize::MAX).unwrap()
+}
+
+/// The seat map once it stops changing, with `#` for the occupied seats and `L` for the empty ones.
+pub fn settled_map(plan: &Grid2D<Tile>, mode: &NeighbourMode) -> String {
+    let (_, now) = settle_seats(plan, mode, usize::MAX).unwrap();
+    let mut map = plan.clone();
+    for (at, tile) in plan.iter() {
+        if *tile != Tile::Floor {
+            map[at] = if now[plan.flat(at.0, at.1)] { Tile::FullSeat } else { Tile::EmptySeat };
+        }
+    }
+    map.to_string()
+}
+
+/// Runs the seating until it stops changing, or gives up with `None` after `max_rounds`. Not every plan settles: big
+/// blocks of seats can keep filling and emptying all at once.
+fn settle(plan: &Grid2D<Tile>, mode: &NeighbourMode, max_rounds: usize) -> Option<Settled> {
+    settle_seats(plan, mode, max_rounds).map(|(settled, _)| settled)
+}
+
+/// As `settle`, but also giving whether each spot ends up occupied, row by row.
+fn settle_seats(plan: &Grid2D<Tile>, mode: &NeighbourMode, max_rounds: usize) -> Option<(Settled, Vec<bool>)> {
+    let now = plan.cells().iter().map(|&tile| tile == Tile::FullSeat).collect();
+    let mut seating = Dense::new(seats(plan, mode), rule(mode), now);
+
+    let mut progress = Progress::new("Day 11 rounds", None);
+    for round in 1..=max_rounds {
+        cancel::check();
+        progress.update(round as u64);
+        if !seating.step() {
+            let settled = Settled {
+                rounds: round.try_into().unwrap(),
+                occupied: seating.population().try_into().unwrap(),
+            };
+            return Some((settled, seating.alive().to_vec()));
+        }
+    }
+    None
+}
+
+/// The seats, each with the seats that count as its neighbours, by `plan.flat`. The floor never changes.
+fn seats(plan: &Grid2D<Tile>, mode: &NeighbourMode) -> Fixed {
+    let is_seat = |tile: &Tile| *tile != Tile::Floor;
+    match mode {
+        // We look through the empty floor when following sight lines.
+        NeighbourMode::Sight => {
+            Fixed::from_grid(plan, is_seat, |i, j| plan.in_sight(i, j, |&tile| tile == Tile::Floor))
+        }
+        NeighbourMode::Adjacent => {
+            Fixed::from_grid(plan, is_seat, |i, j| plan.neighbours8(i, j).filter(|&at| is_seat(&plan[at])))
+        }
+    }
+}
+
+/// Empty seats fill up when no neighbour is occupied, and full ones empty once the tolerance is reached.
+fn rule(mode: &NeighbourMode) -> Rule {
+    let tolerance = match mode {
+        NeighbourMode::Adjacent => 4,
+        NeighbourMode::Sight => 5,
+    };
+    Rule::new(&[0], &(0..tolerance).collect::<Vec<_>>())
+}
+
+/// The seats occupied once the plan settles, looking all around every seat afresh each round. The reference for
+/// `settle`, which works out who can see whom just the once.
+fn settle_naive(plan: &Grid2D<Tile>, mode: &NeighbourMode) -> usize {
+    const DIRECTIONS: [(i64, i64); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+    let (reach, tolerance) = match mode {
+        NeighbourMode::Adjacent => (1, 4),
+        NeighbourMode::Sight => (usize::MAX, 5),
+    };
+    let sees_occupied = |seats: &[Vec<char>], i: usize, j: usize, (di, dj): (i64, i64)| {
+        let (mut i, mut j) = (i64::try_from(i).unwrap(), i64::try_from(j).unwrap());
+        for _ in 0..reach {
+            i += di;
+            j += dj;
+            let seat = usize::try_from(i).ok().zip(usize::try_from(j).ok()).and_then(|(i, j)| seats.get(i)?.get(j));
+            match seat {
+                Some('#') => return true,
+                Some('L') | None => return false,
+                Some(_) => {}
+            }
+        }
+        false
+    };
+
+    let mut seats: Vec<Vec<char>> = plan.rows().map(|row| row.iter().map(Cell::to_char).collect()).collect();
+    loop {
+        let next: Vec<Vec<char>> = seats
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &seat)| {
+                        let seen = DIRECTIONS.iter().filter(|&&d| sees_occupied(&seats, i, j, d)).count();
+                        match seat {
+                            'L' if seen == 0 => '#',
+                            '#' if seen >= tolerance => 'L',
+                            _ => seat,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        if next == seats {
+            return seats.iter().flatten().filter(|&&seat| seat == '#').count();
+        }
+        seats = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> Grid2D<Tile> {
+        Grid2D::parse(11, include_str!("../../data/data11.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_small() {
+        let data_small = &Grid2D::parse(11, include_str!("../../data/data11_small.txt")).unwrap();
+        assert_eq!(Settled { rounds: 6, occupied: 37 }, run_day(data_small, &NeighbourMode::Adjacent));
+        assert_eq!(Settled { rounds: 7, occupied: 26 }, run_day(data_small, &NeighbourMode::Sight));
+        assert_eq!(
+            "#.#L.L#.##\n#LLL#LL.L#\nL.#.L..#..\n#L##.##.L#\n#.#L.LL.LL\n\
+             #.#L#L#.##\n..L.L.....\n#L#L##L#L#\n#.LLLLLL.L\n#.#L#L#.##\n",
+            settled_map(data_small, &NeighbourMode::Adjacent)
+        );
+    }
+
+    #[test]
+    fn test_full() {
+        assert_eq!(Settled { rounds: 84, occupied: 2344 }, run_day(&data(), &NeighbourMode::Adjacent));
+        assert_eq!(Settled { rounds: 87, occupied: 2076 }, run_day(&data(), &NeighbourMode::Sight));
+    }
+}
@@ -1,53 +1,98 @@
 use std::mem;
-use std::time::SystemTime;
+use std::fmt::Write;
 
-use advent2020::{fmt_bright, print_day, print_duration};
+use crate::{input, warn, Answer, ParseError, Rng, Solver};
 
-fn data() -> &'static str {
-    include_str!("../data/data12.txt")
-}
+pub struct Day12;
 
-pub fn run() {
-    print_day(12);
+impl Solver for Day12 {
+    type Input = Vec<Move>;
 
-    let start = SystemTime::now();
+    fn id(&self) -> u32 {
+        12
+    }
 
-    // Let's do this...
-    let mut ferry1 = Boat::new();
-    let mut ferry2 = BoatWithWaypoint::new();
-    voyage(data(), &mut ferry1);
-    voyage(data(), &mut ferry2);
+    fn title(&self) -> &'static str {
+        "Rain Risk"
+    }
 
-    let timed = SystemTime::now().duration_since(start).unwrap();
-    println!("Sailing... {}, {} leagues away.", ferry1.announce(), fmt_bright(&ferry1.manhattan()));
-    println!("Back to port, consult the charts... {}, {} leagues away.", ferry2.announce(), fmt_bright(&ferry2.manhattan()));
-    print_duration(timed);
-}
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_route(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let mut ferry = Boat::new();
+        voyage(input, &mut ferry);
+        ferry.manhattan().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let mut ferry = BoatWithWaypoint::new();
+        voyage(input, &mut ferry);
+        ferry.manhattan().into()
+    }
 
-fn voyage(route: &str, ferry: &mut impl Navigable) {
-    for mv in route.lines() {
-        let digits = mv
-            .chars()
-            .filter(|x| x.is_digit(10) || (*x == '-'))
-            .collect::<String>();
-        let d = digits.parse::<i64>();
-
-        if let Ok(dist) = d {
-            match &mv[0..1] {
-                "N" => ferry.north(dist),
-                "S" => ferry.south(dist),
-                "E" => ferry.east(dist),
-                "W" => ferry.west(dist),
-                "L" => ferry.left(dist),
-                "R" => ferry.right(dist),
-                "F" => ferry.forward(dist),
-                _ => {}
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut route = String::new();
+        for _ in 0..scale.unwrap_or(780) {
+            let action = *rng.pick(&['N', 'S', 'E', 'W', 'L', 'R', 'F']);
+            let value = match action {
+                'L' | 'R' => 90 * rng.between(1, 3),
+                'F' => rng.between(1, 100),
+                _ => rng.between(1, 5),
             };
+            writeln!(route, "{}{}", action, value).unwrap();
+        }
+        Some(route)
+    }
+}
+
+/// One navigation instruction, e.g. `F10` or `R90`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Move {
+    pub action: char,
+    pub value: i64,
+}
+
+/// Reads the navigation instructions, one per line. Turns must be whole quarter turns.
+pub fn parse_route(data: &str) -> Result<Vec<Move>, ParseError> {
+    let mut route = vec![];
+    for (n, line) in input::lines(data) {
+        let error = |col, expected| ParseError::at_line(12, data, n, col, expected);
+
+        let action = line.chars().next().unwrap();
+        if !"NSEWLRF".contains(action) {
+            return Err(error(1, "one of N, S, E, W, L, R or F"));
+        }
+        let rest = &line[action.len_utf8()..];
+        let value = rest.parse::<i64>().map_err(|_| error(2, "a number"))?;
+        if matches!(action, 'L' | 'R') && value.rem_euclid(90) != 0 {
+            return Err(error(2, "a multiple of 90"));
         }
+        route.push(Move { action, value });
     }
+    Ok(route)
 }
 
-trait Navigable {
+/// Follows the navigation instructions.
+pub fn voyage(route: &[Move], ferry: &mut impl Navigable) {
+    for mv in route {
+        let dist = mv.value;
+        match mv.action {
+            'N' => ferry.north(dist),
+            'S' => ferry.south(dist),
+            'E' => ferry.east(dist),
+            'W' => ferry.west(dist),
+            'L' => ferry.left(dist.rem_euclid(360)),
+            'R' => ferry.right(dist.rem_euclid(360)),
+            'F' => ferry.forward(dist),
+            _ => {}
+        }
+    }
+}
+
+/// Something that can follow the navigation instructions.
+pub trait Navigable {
     fn forward(&mut self, d: i64);
     fn north(&mut self, d: i64);
     fn south(&mut self, d: i64);
@@ -55,11 +100,11 @@ trait Navigable {
     fn west(&mut self, d: i64);
     fn left(&mut self, d: i64);
     fn right(&mut self, d: i64);
-    fn announce(&self) -> String;
     fn manhattan(&self) -> i64;
 }
 
-struct BoatWithWaypoint {
+/// The ferry as the instructions really meant it, steering by a waypoint relative to the ship.
+pub struct BoatWithWaypoint {
     x: i64,
     y: i64,
     waypoint_x: i64,
@@ -67,7 +112,7 @@ struct BoatWithWaypoint {
 }
 
 impl BoatWithWaypoint {
-    fn new() -> Self {
+    pub fn new() -> Self {
         BoatWithWaypoint {
             x: 0,
             y: 0,
@@ -77,6 +122,12 @@ impl BoatWithWaypoint {
     }
 }
 
+impl Default for BoatWithWaypoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Navigable for BoatWithWaypoint {
     fn forward(&mut self, d: i64) {
         self.x += d * self.waypoint_x;
@@ -99,7 +150,7 @@ impl Navigable for BoatWithWaypoint {
                 self.waypoint_y *= -1;
             }
             _ => {
-                println!("Unexpected angle {}", angle);
+                warn!("Unexpected angle {}", angle);
                 panic!("Invalid angle.");
             }
         }
@@ -121,7 +172,7 @@ impl Navigable for BoatWithWaypoint {
                 self.waypoint_x *= -1;
             }
             _ => {
-                println!("Unexpected angle {}", angle);
+                warn!("Unexpected angle {}", angle);
                 panic!("Invalid angle.");
             }
         }
@@ -140,29 +191,21 @@ impl Navigable for BoatWithWaypoint {
         self.waypoint_x -= d;
     }
 
-    fn announce(&self) -> String {
-        format!(
-            " -> {},{} (Waypoint {}, {})",
-            self.x,
-            self.y,
-            self.waypoint_x,
-            self.waypoint_y,
-        )
-    }
 
     fn manhattan(&self) -> i64 {
         self.x.abs() + self.y.abs()
     }
 }
 
-struct Boat {
+/// The ferry as first understood, moving itself around.
+pub struct Boat {
     x: i64,
     y: i64,
     dir: i64,
 }
 
 impl Boat {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Boat {
             x: 0,
             y: 0,
@@ -171,6 +214,12 @@ impl Boat {
     }
 }
 
+impl Default for Boat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Navigable for Boat {
     fn forward(&mut self, d: i64) {
         match self.dir {
@@ -187,7 +236,7 @@ impl Navigable for Boat {
                 self.west(d);
             }
             _ => {
-                println!("Direction! {}", self.dir);
+                warn!("Direction! {}", self.dir);
                 panic!("Unexpected direction.");
             }
         }
@@ -222,23 +271,21 @@ impl Navigable for Boat {
         self.x -= d;
     }
 
-    fn announce(&self) -> String {
-        format!(
-            " -> {},{}",
-            self.x,
-            self.y,
-        )
-    }
 
     fn manhattan(&self) -> i64 {
         self.x.abs() + self.y.abs()
     }
 }
 
+#[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    fn data() -> &'static str {
+        include_str!("../../data/data12.txt")
+    }
+
     #[test]
     fn test_small() {
         let data_small = "F10
@@ -248,8 +295,9 @@ R90
 F11";
         let mut ferry1 = Boat::new();
         let mut ferry2 = BoatWithWaypoint::new();
-        voyage(&data_small, &mut ferry1);
-        voyage(&data_small, &mut ferry2);
+        let route = parse_route(data_small).unwrap();
+        voyage(&route, &mut ferry1);
+        voyage(&route, &mut ferry2);
         assert_eq!((17, -8, 25), (ferry1.x, ferry1.y, ferry1.manhattan()));
         assert_eq!((214, -72, 286), (ferry2.x, ferry2.y, ferry2.manhattan()));
     }
@@ -258,9 +306,17 @@ F11";
     fn test_all() {
         let mut ferry1 = Boat::new();
         let mut ferry2 = BoatWithWaypoint::new();
-        voyage(data(), &mut ferry1);
-        voyage(data(), &mut ferry2);
+        let route = parse_route(data()).unwrap();
+        voyage(&route, &mut ferry1);
+        voyage(&route, &mut ferry2);
         assert_eq!((-268, -240, 508), (ferry1.x, ferry1.y, ferry1.manhattan()));
         assert_eq!((-13588, -17173, 30761), (ferry2.x, ferry2.y, ferry2.manhattan()));
     }
+
+    #[test]
+    fn test_bad() {
+        assert_eq!(Err(ParseError::new(12, 2, 2, "R45", "a multiple of 90")), parse_route("F10\nR45"));
+        assert_eq!(Err(ParseError::new(12, 1, 1, "X3", "one of N, S, E, W, L, R or F")), parse_route("X3"));
+        assert_eq!(Err(ParseError::new(12, 3, 2, "Fx", "a number")), parse_route("F1\n\nFx"));
+    }
 }
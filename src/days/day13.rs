@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use crate::{cancel, input, debug, trace, Answer, ParseError, Rng, Solver};
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Timetable;
+
+    fn id(&self) -> u32 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Shuttle Search"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = input::lines(data);
+        let (n, start) = lines.next().ok_or_else(|| ParseError::at(13, data, data.len(), "a start time"))?;
+        let start = start.parse().map_err(|_| ParseError::at_line(13, data, n, 1, "a start time"))?;
+        let (n, line) = lines.next().ok_or_else(|| ParseError::at(13, data, data.len(), "a list of buses"))?;
+
+        let mut buses = vec![];
+        let mut col = 1;
+        for field in line.split(',') {
+            buses.push(match field {
+                "x" => None,
+                _ => match field.parse() {
+                    Ok(bus) if bus > 0 => Some(bus),
+                    _ => return Err(ParseError::at_line(13, data, n, col, "a bus id above 0, or x")),
+                },
+            });
+            col += field.chars().count() + 1;
+        }
+        if buses.iter().all(Option::is_none) {
+            return Err(ParseError::at_line(13, data, n, 1, "at least one bus"));
+        }
+        if let Some((n, _)) = lines.next() {
+            return Err(ParseError::at_line(13, data, n, 1, "nothing after the buses"));
+        }
+        Ok(Timetable { start, buses })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        waiting_times(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        solve_competition(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // Buses with different prime ids, so the competition always has an answer, and few enough of them that it fits
+        // in a u64.
+        const PRIMES: [u64; 20] = [13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 409, 433, 631, 821];
+        let slots = scale.unwrap_or(60).max(1);
+        let mut primes = PRIMES.to_vec();
+        rng.shuffle(&mut primes);
+        let mut product = 1_u64;
+        let mut buses = vec![];
+        for p in primes {
+            match product.checked_mul(p) {
+                Some(next) if next < 1 << 60 && buses.len() < slots.min(9) => {
+                    product = next;
+                    buses.push(p);
+                }
+                _ => break,
+            }
+        }
+
+        // The first slot always has a bus.
+        let mut positions: Vec<usize> = (1..slots).collect();
+        rng.shuffle(&mut positions);
+        positions.insert(0, 0);
+        let mut timetable = vec![String::from("x"); slots];
+        for (bus, &pos) in buses.iter().zip(&positions) {
+            timetable[pos] = bus.to_string();
+        }
+        Some(format!("{}\n{}\n", rng.between(100_000, 10_000_000), timetable.join(",")))
+    }
+}
+
+/// When we get to the bus stop, and the buses in the order they're listed, with `None` for each `x`.
+#[derive(Debug, PartialEq)]
+pub struct Timetable {
+    pub start: u64,
+    pub buses: Vec<Option<u64>>,
+}
+
+/// The earliest time at which each bus departs at its offset in the list.
+pub fn solve_competition(timetable: &Timetable) -> u64 {
+    // The start time doesn't matter for the competition.
+    let offset_buses: Vec<(u64, u64)> = timetable
+        .buses
+        .iter()
+        .enumerate()
+        .filter_map(|(i, bus)| bus.map(|bus| (bus, i as u64)))
+        .collect();
+
+    let mut t = 0;
+    let mut iters = 0;
+    // The first bus to fit sets the step, so this only stays at 1 for the first check.
+    let mut delta = 1;
+    let mut alignment: HashMap<u64, u64> = HashMap::new(); // Track alignments.
+    loop {
+        cancel::check();
+        let mut matched = true;
+        for (bus, offset) in &offset_buses {
+            if (t + offset) % bus == 0  {
+                // This bus *did* fit. Lock in this alignment.
+                if !alignment.contains_key(bus) {
+                    alignment.insert(*bus, t);
+                    delta *= bus;
+                    trace!("Aligned with {} after {} iterations. (New delta: {})", bus, iters, delta);
+                }
+            } else {
+                // This bus didn't fit. Stop checking and move on.
+                matched = false;
+            }
+        }
+
+        if matched {
+            debug!("Found a solution after {} iterations.", iters);
+            return t;
+        }
+        iters += 1;
+        t += delta;
+    }
+}
+
+/// The first bus to leave after the start time, times how long we wait for it.
+pub fn waiting_times(timetable: &Timetable) -> u64 {
+    let start = timetable.start;
+    let buses: Vec<u64> = timetable.buses.iter().flatten().copied().collect();
+
+    let mut t = start;
+    loop {
+        cancel::check();
+        // Check all the buses at the current time.
+        for bus in &buses {
+            if t.is_multiple_of(*bus) {
+                debug!("[{}] After waiting {}, bus {} is here!", t, t - start, bus);
+                return (t - start) * bus;
+            }
+        }
+        t += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data13.txt")
+    }
+
+    #[test]
+    fn test_small() {
+        let input = Day13.parse("939\n7,13,x,x,59,x,31,19\n").unwrap();
+        assert_eq!((939, Some(13), None), (input.start, input.buses[1], input.buses[2]));
+        assert_eq!((295, 1_068_781), (waiting_times(&input), solve_competition(&input)));
+        assert_eq!(3417, solve_competition(&Day13.parse("0\n17,x,13,19\n").unwrap()));
+        // A first slot with no bus just moves everything a minute on.
+        assert_eq!(3416, solve_competition(&Day13.parse("0\nx,17,x,13,19\n").unwrap()));
+    }
+
+    #[test]
+    fn test_bad_timetable() {
+        let err = |data: &str| Day13.parse(data).map_err(|err| (err.line, err.col)).unwrap_err();
+        assert_eq!((1, 1), err(""));
+        assert_eq!((1, 1), err("x\n"));
+        assert_eq!((2, 1), err("1\n"));
+        assert_eq!((2, 3), err("1\n2,a\n"));
+        assert_eq!((2, 5), err("1\n7,x,0\n"));
+        assert_eq!((2, 1), err("1\nx,x\n"));
+        assert_eq!((1, 1), err("a b c\n"));
+    }
+
+    #[test]
+    fn test_all() {
+        let input = Day13.parse(data()).unwrap();
+        assert_eq!(Answer::from(3865), Day13.part1(&input));
+        assert_eq!(Answer::from(415_579_909_629_976_u64), Day13.part2(&input));
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::Write;
+
+use crate::{input, Answer, ParseError, Rng, Solver};
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = Vec<Command>;
+
+    fn id(&self) -> u32 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Docking Data"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_program(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        run_decode(input, DecodeMode::ContentsMask).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        run_decode(input, DecodeMode::MemoryMask).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // `scale` masks, each with a few writes. No more than nine floating bits, so each write in version 2 touches at
+        // most 512 addresses.
+        let mut program = String::new();
+        for _ in 0..scale.unwrap_or(100) {
+            let mut mask: Vec<char> = (0..36).map(|_| if rng.chance(0.5) { '1' } else { '0' }).collect();
+            for _ in 0..rng.below(10) {
+                mask[rng.below(36)] = 'X';
+            }
+            writeln!(program, "mask = {}", mask.iter().collect::<String>()).unwrap();
+            for _ in 0..rng.between(1, 6) {
+                writeln!(program, "mem[{}] = {}", rng.below(65536), rng.between(0, (1 << 36) - 1)).unwrap();
+            }
+        }
+        Some(program)
+    }
+}
+
+/// One line of the initialisation program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// `mask = XXX10X1XX0X...`, most significant bit first.
+    Mask([char; 36]),
+    /// `mem[7] = 101`
+    Write { address: u64, value: u64 },
+}
+
+/// Reads the initialisation program, one command per line.
+pub fn parse_program(data: &str) -> Result<Vec<Command>, ParseError> {
+    let mut program = vec![];
+    for (n, line) in input::lines(data) {
+        let error = |col, expected| ParseError::at_line(14, data, n, col, expected);
+
+        if let Some(bits) = line.strip_prefix("mask = ") {
+            let mut mask = ['X'; 36];
+            for (j, c) in bits.chars().enumerate() {
+                if j >= 36 || !matches!(c, '0' | '1' | 'X' | 'x') {
+                    return Err(error(8 + j, "36 bits of 0, 1 or X"));
+                }
+                mask[j] = c;
+            }
+            if bits.len() < 36 {
+                return Err(error(8 + bits.len(), "36 bits of 0, 1 or X"));
+            }
+            program.push(Command::Mask(mask));
+        } else if let Some(rest) = line.strip_prefix("mem[") {
+            // Commands like : mem[7] = 101
+            let (address, value) = rest.split_once("] = ").ok_or_else(|| error(5, "an address in mem[...] = "))?;
+            let address = address.parse().map_err(|_| error(5, "an address"))?;
+            let value = value.parse().map_err(|_| error(line.len() - value.len() + 1, "a number"))?;
+            program.push(Command::Write { address, value });
+        } else {
+            return Err(error(1, "mask or mem"));
+        }
+    }
+    Ok(program)
+}
+
+/// Runs the initialisation program, giving the sum of everything left in memory.
+pub fn run_decode(prog: &[Command], mode: DecodeMode) -> u64{
+    let mut decoder = Decoder::new(mode);
+    for command in prog {
+        decoder.process(command);
+    }
+
+    decoder.sums()
+}
+
+/// Which version of the decoder chip to emulate.
+pub enum DecodeMode {
+    /// The mask applies to the values written.
+    ContentsMask,
+    /// The mask applies to the addresses, with floating bits writing to every combination.
+    MemoryMask,
+}
+
+struct Decoder {
+    mode: DecodeMode,
+    mem: HashMap<u64, u64>,
+    mask: [char; 36],
+}
+
+impl Decoder {
+    fn new(mode: DecodeMode) -> Self {
+        Decoder {
+            mode,
+            mem: HashMap::new(),
+            mask: ['x'; 36],
+        }
+    }
+
+    fn process(&mut self, command: &Command) {
+        match *command {
+            Command::Mask(mask) => self.mask = mask,
+            Command::Write { address, value } => match self.mode {
+                DecodeMode::ContentsMask => self.process_contents_mode(address, value),
+                DecodeMode::MemoryMask => self.process_memory_mode(address, value),
+            },
+        }
+    }
+    
+    fn process_memory_mode(&mut self, mem: u64, decimal_val: u64) {
+        // Need to transform memory address into multiple.
+        let mem_bits: Vec<_> = format!("{:#036b}", mem).chars().collect();
+
+        let mut addresses: Vec<u64> = vec![0]; // We always start with one address
+        for (i, c) in self.mask.iter().enumerate() {
+            let addition: u64 = 2_u64.pow(35_u32 - u32::try_from(i).unwrap());
+            let mut next_addresses = vec![]; 
+            match c {
+                // If '1', add addition unconditionally.
+                '1' => {
+                    for address in &addresses {
+                        next_addresses.push(*address + addition);
+                    }
+                },
+                // If '0' *and* the mem bit is one, add it.
+                '0' => {
+                    if mem_bits[i] == '1' {
+                        for address in &addresses {
+                            next_addresses.push(*address + addition);
+                        }
+                    } else {
+                        for address in &addresses {
+                            next_addresses.push(*address);
+                        }
+                    }
+                }
+                // The interesting case we push both with and without.
+                'X' | 'x' => {
+                    for address in &addresses {
+                        next_addresses.push(*address);
+                        next_addresses.push(*address + addition);
+                    }
+                },
+                _ => panic!("Unexpected mask"),
+            }
+
+            // Now copy the addresses.
+            addresses = next_addresses;
+        }
+
+
+        // We should now have a whole heap o' addresses to write to.
+        for address in addresses {
+            self.mem.insert(address, decimal_val);
+        }
+    }
+
+    fn process_contents_mode(&mut self, mem: u64, decimal_val: u64) {
+        let val = format!("{:036b}", decimal_val);
+
+        // Need to mask the value.
+        let mut new_val = 0;
+        for (i, c) in val.chars().enumerate() {
+            let addition: u64 = 2_u64.pow(35_u32 - u32::try_from(i).unwrap());
+            let add_val = match self.mask[i] {
+                '1' => addition,
+                '0' => 0,
+                'X' | 'x' => if c == '1' { addition } else { 0 },
+                _ => panic!("Unexpected mask"),
+            };
+            new_val += add_val;
+        }
+
+        self.mem.insert(mem, new_val);
+    }
+
+    fn sums(&self) -> u64 {
+        let mut sum = 0;
+        for v in self.mem.values() {
+            sum += v;
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data14.txt")
+    }
+
+    #[test]
+    fn test_small() {
+        let data_small = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+        let data_small2 = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+        assert_eq!(165, run_decode(&parse_program(data_small).unwrap(), DecodeMode::ContentsMask));
+        assert_eq!(208, run_decode(&parse_program(data_small2).unwrap(), DecodeMode::MemoryMask));
+    }
+
+    #[test]
+    fn test_all() {
+        let program = parse_program(data()).unwrap();
+        assert_eq!(4_297_467_072_083, run_decode(&program, DecodeMode::ContentsMask));
+        assert_eq!(5_030_603_328_768, run_decode(&program, DecodeMode::MemoryMask));
+    }
+
+    #[test]
+    fn test_bad() {
+        let err = parse_program("mask = XXXX\nmem[8] = 11").unwrap_err();
+        assert_eq!((1, 12, "36 bits of 0, 1 or X"), (err.line, err.col, err.expected.as_str()));
+
+        let err = parse_program("mem[8] = 11\nmem[7] = ten").unwrap_err();
+        assert_eq!(ParseError::new(14, 2, 10, "mem[7] = ten", "a number"), err);
+
+        let err = parse_program("mem[x] = 1").unwrap_err();
+        assert_eq!((1, 5), (err.line, err.col));
+    }
+}
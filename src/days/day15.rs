@@ -0,0 +1,174 @@
+use std::convert::{TryFrom, TryInto};
+
+use crate::checkpoint::Checkpoint;
+use crate::params::Param;
+use crate::progress::Progress;
+use crate::{cancel, debug, input_hash, params, Answer, ParseError, Rng, Solver};
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Vec<u32>;
+
+    fn id(&self) -> u32 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Rambunctious Recitation"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "part1_turns", valid: params::positive::<u32> },
+            Param { name: "part2_turns", valid: params::positive::<u32> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        let line = data.trim();
+        let mut offset = data.find(line).unwrap_or(0);
+        let mut starting = vec![];
+        for number in line.split(',') {
+            match number.trim().parse() {
+                Ok(n) => starting.push(n),
+                Err(_) => return Err(ParseError::at(15, data, offset, "a starting number")),
+            }
+            offset += number.len() + 1;
+        }
+        Ok(starting)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        repeat_to(input, turns("part1_turns", 2020)).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        repeat_to(input, turns("part2_turns", 30_000_000)).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let count = scale.unwrap_or(6).max(1);
+        let mut starting: Vec<usize> = (0..count * 4).collect();
+        rng.shuffle(&mut starting);
+        starting.truncate(count);
+        Some(starting.iter().map(ToString::to_string).collect::<Vec<_>>().join(",") + "\n")
+    }
+
+    fn reference(&self, input: &Self::Input, part: u32) -> Option<Answer> {
+        // Part 2 is far too many turns to search back through.
+        (part == 1).then(|| speak_naive(input, turns("part1_turns", 2020)).into())
+    }
+}
+
+/// How many turns to play for, from the parameter `name`. There's no answer before the first turn.
+fn turns(name: &str, default: u32) -> u32 {
+    params::get(15, name, default)
+}
+
+/// The number spoken on turn `target` of the memory game.
+pub fn repeat_to(starting: &[u32], target: u32) -> u32 {
+    let key = input_hash(&format!("{:?} {}", starting, target));
+    repeat_with(starting, target, &mut Checkpoint::new(15, key))
+}
+
+/// Plays the memory game, carrying on from the checkpoint's snapshot if it has one and saving more along the way.
+fn repeat_with(starting: &[u32], target: u32, checkpoint: &mut Checkpoint) -> u32 {
+    // Every number spoken is an age, so less than `target`, unless it's one of the starting numbers.
+    let size = starting.iter().map(|&n| n as usize + 1).max().unwrap_or(0).max(target as usize);
+    let (start, mut last, mut cache) = match checkpoint.resume() {
+        Some(snapshot) if snapshot.state.len() == size => {
+            debug!("Carrying on from turn {}", snapshot.done);
+            (u32::try_from(snapshot.done).unwrap(), u32::try_from(snapshot.extra).unwrap(), snapshot.state)
+        }
+        _ => (0, 0_u32, vec![u32::MAX; size]),
+    };
+    let mut progress = Progress::new("Day 15 turns", Some(u64::from(target)));
+
+    for i in start..target {
+        if i % 65536 == 0 {
+            cancel::check();
+            progress.update(u64::from(i));
+            if checkpoint.due() {
+                checkpoint.save(u64::from(i), u64::from(last), &cache);
+            }
+        }
+
+        let next;
+        if i < starting.len().try_into().unwrap() {
+            next = starting[i as usize];
+        } else if cache[last as usize] == u32::MAX {
+            next = 0;
+        } else {
+            next = i - cache[last as usize];
+        }
+
+        // Make a note of the last number, if anything has been said yet.
+        if i > 0 {
+            cache[last as usize] = i;
+        }
+        last = next;
+    }
+    last 
+}
+
+/// The number spoken on turn `target`, searching back through everything said so far for when the last number was
+/// last said. The reference for `repeat_to`.
+fn speak_naive(starting: &[u32], target: u32) -> u32 {
+    let mut spoken: Vec<u32> = vec![];
+    for turn in 0..usize::try_from(target).unwrap() {
+        let next = match (starting.get(turn), spoken.split_last()) {
+            (Some(&n), _) => n,
+            (None, Some((last, before))) => {
+                before.iter().rposition(|n| n == last).map_or(0, |at| u32::try_from(before.len() - at).unwrap())
+            }
+            (None, None) => 0,
+        };
+        spoken.push(next);
+    }
+    spoken.last().copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::checkpoint::{self, Snapshot};
+
+    #[test]
+    fn test_2020() {
+        let data = vec![11,0,1,10,5,19];
+        assert_eq!(870, repeat_to(&data, 2020));
+    }
+
+    #[test]
+    fn test_without_zero() {
+        // Nothing has been said before the first turn, zero included.
+        assert_eq!(1, repeat_to(&[1, 3, 2], 2020));
+        assert_eq!(10, repeat_to(&[2, 1, 3], 2020));
+        assert_eq!(1836, repeat_to(&[3, 1, 2], 2020));
+    }
+
+    #[test]
+    fn test_resume() {
+        let data = vec![11,0,1,10,5,19];
+        let spoken = repeat_to(&data, 200_000);
+        let (mut checkpoint, saved) = checkpoint::tests::saving(15, 1, "day15", None);
+        assert_eq!(spoken, repeat_with(&data, 200_000, &mut checkpoint));
+        // The last snapshot is from turn 196608, and carrying on from it gets to the same place.
+        let snapshot = saved();
+        assert_eq!(196_608, snapshot.done);
+        let finished = Snapshot { done: 200_000, extra: 42, ..snapshot.clone() };
+        let (mut checkpoint, _) = checkpoint::tests::saving(15, 1, "day15-resumed", Some(snapshot));
+        assert_eq!(spoken, repeat_with(&data, 200_000, &mut checkpoint));
+        // A snapshot of the last turn leaves nothing to do.
+        let (mut checkpoint, _) = checkpoint::tests::saving(15, 1, "day15-finished", Some(finished));
+        assert_eq!(42, repeat_with(&data, 200_000, &mut checkpoint));
+    }
+
+    #[test]
+    fn test_30mill() {
+        let data = vec![11,0,1,10,5,19];
+        assert_eq!(9136, repeat_to(&data, 30_000_000));
+    }
+}
@@ -2,33 +2,47 @@ use pest::Parser;
 use pest_derive::Parser;
 
 use std::collections::HashMap;
-use std::time::SystemTime;
 
-use advent2020::{fmt_bright, print_day, print_duration};
+use crate::{debug, Answer, ParseError, Rng, Solver};
 
-fn data() -> &'static str {
-    include_str!("../data/data16.txt")
-}
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input = TicketNotes;
 
-pub fn run() {
-    print_day(16);
+    fn id(&self) -> u32 {
+        16
+    }
 
-    let start = SystemTime::now();
+    fn title(&self) -> &'static str {
+        "Ticket Translation"
+    }
 
-    let (res1, res2) = parse_tickets(data());
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        import_tickets(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        error_rate(input).into()
+    }
 
-    let timed = SystemTime::now().duration_since(start).unwrap();
-    println!("Error rate across all tickets is {}, my departure product is {} .", fmt_bright(&res1), fmt_bright(&res2));
-    print_duration(timed);
+    fn part2(&self, input: &Self::Input) -> Answer {
+        departure_product(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        Some(generate_notes(rng, scale.unwrap_or(240)))
+    }
 }
 
 #[derive(Debug)]
-struct Ticket {
-    values: Vec<u64>
+pub struct Ticket {
+    pub values: Vec<u64>
 }
 
 impl Ticket {
-    fn error_rate(&self, rules: &[TicketRule]) -> Option<u64> {
+    /// The sum of the values that fit no rule at all, or `None` if every value fits some rule.
+    pub fn error_rate(&self, rules: &[TicketRule]) -> Option<u64> {
         let mut rate = 0;
         let mut valid = true;
         for value in &self.values {
@@ -55,25 +69,25 @@ impl Ticket {
 }
 
 #[derive(Debug)]
-struct Range {
-    min: u64,
-    max: u64
+pub struct Range {
+    pub min: u64,
+    pub max: u64
 }
 
 impl Range {
-    fn fits(&self, v: u64) -> bool {
+    pub fn fits(&self, v: u64) -> bool {
         v >= self.min && v <= self.max
     }
 }
 
 #[derive(Debug)]
-struct TicketRule {
-    name: String,
-    ranges: Vec<Range>
+pub struct TicketRule {
+    pub name: String,
+    pub ranges: Vec<Range>
 }
 
 impl TicketRule {
-    fn fits_any(&self, v: u64) -> bool {
+    pub fn fits_any(&self, v: u64) -> bool {
         for r in &self.ranges {
             if r.fits(v) {
                 return true;
@@ -83,9 +97,17 @@ impl TicketRule {
     }
 }
 
-fn import_tickets(data: &str) -> (Ticket, Vec<Ticket>, Vec<TicketRule>) {
+/// Everything in the notes.
+#[derive(Debug)]
+pub struct TicketNotes {
+    pub mine: Ticket,
+    pub nearby: Vec<Ticket>,
+    pub rules: Vec<TicketRule>,
+}
+
+pub fn import_tickets(data: &str) -> Result<TicketNotes, ParseError> {
     let file = DParser::parse(Rule::file, data)
-        .expect("unsuccesful parse")
+        .map_err(|e| ParseError::from_pest(16, data, &e))?
         .next()
         .unwrap();
 
@@ -97,7 +119,7 @@ fn import_tickets(data: &str) -> (Ticket, Vec<Ticket>, Vec<TicketRule>) {
     for line in file.into_inner() {
         match line.as_rule() {
             Rule::field => {
-                let mut new_rule = TicketRule { name: "".to_string(), ranges: vec![]};
+                let mut new_rule = TicketRule { name: String::new(), ranges: vec![]};
                 for part in line.into_inner() {
                     match part.as_rule() {
                         Rule::fieldname => { new_rule.name = part.as_str().to_string(); },
@@ -141,29 +163,85 @@ fn import_tickets(data: &str) -> (Ticket, Vec<Ticket>, Vec<TicketRule>) {
         }
     }
 
-    (my_ticket, tickets, rules)
+    Ok(TicketNotes {
+        mine: my_ticket,
+        nearby: tickets,
+        rules,
+    })
 }
 
-fn parse_tickets(data: &str) -> (u64, u64) {
-
-    let (my_ticket, tickets, rules) = import_tickets(data);
-
-    let mut error_rate = 0;
-    let mut good_tickets = vec![];
-    for ticket in tickets {
-        if let Some(ticket_err) = ticket.error_rate(&rules) {
-            error_rate += ticket_err;
-        } else {
-            good_tickets.push(ticket);
+const FIELDS: [&str; 20] = [
+    "departure location", "departure station", "departure platform", "departure track", "departure date",
+    "departure time", "arrival location", "arrival station", "arrival platform", "arrival track", "class",
+    "duration", "price", "route", "row", "seat", "train", "type", "wagon", "zone",
+];
+
+/// Makes up notes with `nearby` tickets, about a quarter of them invalid.
+///
+/// The rules nest, so that field `i` takes values up to about `100 * (i + 1)`, with a small gap that no ticket uses.
+/// Each field also has one valid ticket with a value too big for the field before it, which leaves only one way to
+/// match fields to positions, found by peeling them off from the first. The departure fields come first, keeping
+/// their values small enough to multiply together.
+pub fn generate_notes(rng: &mut Rng, nearby: usize) -> String {
+    let fields = FIELDS.len() as u64;
+    let mut rules: Vec<String> = FIELDS
+        .iter()
+        .zip(0..)
+        .map(|(name, i)| format!("{}: 1-{} or {}-{}", name, 100 * i + 49, 100 * i + 60, 100 * (i + 1)))
+        .collect();
+    rng.shuffle(&mut rules);
+
+    let mut positions: Vec<u64> = (0..fields).collect();
+    rng.shuffle(&mut positions);
+    let ticket = |rng: &mut Rng, pinned: bool| -> Vec<u64> {
+        positions
+            .iter()
+            .map(|&i| if pinned && i > 0 { rng.between(100 * i + 1, 100 * i + 49) } else { rng.between(1, 100 * i + 49) })
+            .collect()
+    };
+    let show = |values: &[u64]| values.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+
+    let mut tickets = vec![ticket(rng, true)];
+    while tickets.len() < nearby.max(1) {
+        let mut values = ticket(rng, false);
+        if rng.chance(0.25) {
+            let bad = rng.below(values.len());
+            values[bad] = rng.between(100 * fields + 1, 100 * fields + 999);
         }
+        tickets.push(values);
     }
+    rng.shuffle(&mut tickets);
+
+    format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}\n",
+        rules.join("\n"),
+        show(&ticket(rng, false)),
+        tickets.iter().map(|t| show(t)).collect::<Vec<_>>().join("\n")
+    )
+}
+
+/// The sum of every nearby ticket value that fits no rule.
+pub fn error_rate(notes: &TicketNotes) -> u64 {
+    notes.nearby.iter().filter_map(|ticket| ticket.error_rate(&notes.rules)).sum()
+}
+
+/// The product of the fields on my ticket whose names start with "departure", working out which field is which from
+/// the valid nearby tickets.
+pub fn departure_product(notes: &TicketNotes) -> u64 {
+    let TicketNotes {
+        mine: my_ticket,
+        nearby: tickets,
+        rules,
+    } = notes;
+
+    let good_tickets: Vec<&Ticket> = tickets.iter().filter(|ticket| ticket.error_rate(rules).is_none()).collect();
     let field_count = my_ticket.values.len();
 
     let mut fields = HashMap::new();
     let mut can_be: HashMap<&String, Vec<usize>> = HashMap::new();
 
     // Initialise fields map
-    for rule in &rules {
+    for rule in rules {
         can_be.insert(&rule.name, vec![]);
     }
 
@@ -175,7 +253,7 @@ fn parse_tickets(data: &str) -> (u64, u64) {
             all_values.push(ticket.values[i]);
         }
         // Now - which rules match all values?
-        for rule in &rules {
+        for rule in rules {
             let mut can_match = true;
             for val in &all_values {
                 if !rule.fits_any(*val) {
@@ -212,7 +290,7 @@ fn parse_tickets(data: &str) -> (u64, u64) {
             }
             // If there's exactly one possibility, use it!
             if possibilities.len() == 1 {
-                let found = possibilities.get(0).unwrap();
+                let found = possibilities.first().unwrap();
                 matched_indices.insert(*found, field_name);
                 matched.insert(field_name, *found);
                 updates += 1;
@@ -229,11 +307,11 @@ fn parse_tickets(data: &str) -> (u64, u64) {
         if k.starts_with("departure") {
             let val = my_ticket.values.get(v).unwrap();
             departure_product *= val;
-            println!("Field {} -> {}", v, k);
+            debug!("Field {} -> {}", v, k);
         }
     }
 
-    (error_rate, departure_product)
+    departure_product
 }
 
 
@@ -241,20 +319,30 @@ fn parse_tickets(data: &str) -> (u64, u64) {
 #[grammar = "parsers/day16.pest"]
 pub struct DParser {}
 
+#[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    fn data() -> &'static str {
+        include_str!("../../data/data16.txt")
+    }
+
+    fn report(data: &str) -> (u64, u64) {
+        let notes = import_tickets(data).unwrap();
+        (error_rate(&notes), departure_product(&notes))
+    }
+
     #[test]
     fn test_small() {
-        let data_small = include_str!("../data/data16_small.txt");
-        let data_small2 = include_str!("../data/data16_small2.txt");
-        assert_eq!((71,1), parse_tickets(&data_small));
-        assert_eq!((0,1), parse_tickets(&data_small2));
+        let data_small = include_str!("../../data/data16_small.txt");
+        let data_small2 = include_str!("../../data/data16_small2.txt");
+        assert_eq!((71, 1), report(data_small));
+        assert_eq!((0, 1), report(data_small2));
     }
 
     #[test]
     fn test_all() {
-        assert_eq!((27850, 491924517533), parse_tickets(data()));
+        assert_eq!((27850, 491_924_517_533), report(data()));
     }
 }
@@ -0,0 +1,177 @@
+use std::convert::TryFrom;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::life::{self, Automaton, Lattice, Rule, Sparse};
+use crate::params::Param;
+use crate::progress::Progress;
+use crate::{debug, params, trace, Answer, Grid2D, ParseError, Rng, Solver};
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Input = Grid2D<bool>;
+
+    fn id(&self) -> u32 {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Conway Cubes"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "cycles", valid: params::reads_as::<usize> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        Grid2D::parse(17, data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        run_cube(input, params::get(17, "cycles", 6), false).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        run_cube(input, params::get(17, "cycles", 6), true).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let size = scale.unwrap_or(8);
+        let mut slice = String::new();
+        for _ in 0..size {
+            slice.extend((0..size).map(|_| if rng.chance(0.5) { '#' } else { '.' }));
+            slice.push('\n');
+        }
+        Some(slice)
+    }
+
+    fn reference(&self, input: &Self::Input, part: u32) -> Option<Answer> {
+        Some(active_after(input, params::get(17, "cycles", 6), part == 2).len().into())
+    }
+
+    fn visualise(&self, input: &Self::Input) -> Option<String> {
+        Some(slices(input, params::get(17, "cycles", 6)))
+    }
+}
+
+/// The active cubes after `iters` cycles, in three dimensions or, `with_w`, four.
+pub fn run_cube(init: &Grid2D<bool>, iters: usize, with_w: bool) -> u32 {
+    let cubes = init
+        .iter()
+        .filter(|(_, &cube)| cube)
+        .map(|((x, y), _)| [i64::try_from(x).unwrap(), i64::try_from(y).unwrap(), 0, 0]);
+    let active = if with_w {
+        cycle(&mut Sparse::new(Lattice::<4>::new(), Rule::CONWAY, cubes.collect()), iters)
+    } else {
+        let cubes = cubes.map(|[x, y, z, _]| [x, y, z]).collect();
+        cycle(&mut Sparse::new(Lattice::<3>::new(), Rule::CONWAY, cubes), iters)
+    };
+    u32::try_from(active).unwrap()
+}
+
+/// Runs the pocket dimension for `iters` cycles, giving how many cubes are left active.
+fn cycle(pocket: &mut impl Automaton, iters: usize) -> usize {
+    let mut progress = Progress::new("Day 17 cycles", Some(iters as u64));
+    let active = life::populations(pocket, iters, |iteration, pocket| {
+        progress.update(iteration as u64);
+        trace!("[{}] {} active.", iteration, pocket.population());
+    });
+    debug!("[{}] {} active.", iters, active[iters]);
+    active[iters]
+}
+
+/// Each slice through the three dimensional pocket after `iters` cycles, labelled by `z` as in the puzzle, cut down to
+/// the active cubes.
+pub fn slices(init: &Grid2D<bool>, iters: usize) -> String {
+    let active = active_after(init, iters, false);
+    let range = |axis: usize| {
+        let values = active.iter().map(|cube| cube[axis]);
+        values.clone().min().unwrap_or(0)..=values.max().unwrap_or(0)
+    };
+
+    let mut drawn = String::new();
+    for z in range(2) {
+        writeln!(drawn, "z={}", z).unwrap();
+        for x in range(0) {
+            drawn.extend(range(1).map(|y| if active.contains(&vec![x, y, z]) { '#' } else { '.' }));
+            drawn.push('\n');
+        }
+        drawn.push('\n');
+    }
+    drawn
+}
+
+/// The active cubes after `iters` cycles, keeping just a set of the active ones and counting their neighbours in a map.
+/// The reference for `run_cube`, which runs the shared Life engine instead.
+fn active_after(init: &Grid2D<bool>, iters: usize, with_w: bool) -> HashSet<Vec<i64>> {
+    let dims: u32 = if with_w { 4 } else { 3 };
+    let mut active: HashSet<Vec<i64>> = HashSet::new();
+    for ((x, y), &cube) in init.iter() {
+        if cube {
+            let (x, y) = (i64::try_from(x).unwrap(), i64::try_from(y).unwrap());
+            active.insert([x, y, 0, 0][..dims as usize].to_vec());
+        }
+    }
+
+    // Every step to a neighbour, as the digits of a number in base 3.
+    let steps: Vec<Vec<i64>> = (0..3_i64.pow(dims))
+        .map(|n| {
+            (0..dims)
+                .scan(n, |rest, _| {
+                    let digit = *rest % 3 - 1;
+                    *rest /= 3;
+                    Some(digit)
+                })
+                .collect::<Vec<i64>>()
+        })
+        .filter(|step| step.iter().any(|&d| d != 0))
+        .collect();
+
+    for _ in 0..iters {
+        let mut counts: HashMap<Vec<i64>, usize> = HashMap::new();
+        for cube in &active {
+            for step in &steps {
+                *counts.entry(cube.iter().zip(step).map(|(c, d)| c + d).collect()).or_insert(0) += 1;
+            }
+        }
+        active = counts
+            .into_iter()
+            .filter(|(cube, n)| *n == 3 || (*n == 2 && active.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> Grid2D<bool> {
+        Grid2D::parse(17, include_str!("../../data/data17.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_small() {
+        let data_small = &Grid2D::parse(17, ".#.
+..#
+###").unwrap();
+
+        assert_eq!(112, run_cube(data_small, 6, false));
+        assert_eq!(848, run_cube(data_small, 6, true));
+        assert_eq!(
+            "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.\n\n",
+            slices(data_small, 1)
+        );
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(213, run_cube(&data(), 6, false));
+        assert_eq!(1624, run_cube(&data(), 6, true));
+    }
+}
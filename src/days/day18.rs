@@ -0,0 +1,266 @@
+use std::fmt::Write;
+
+use pest::iterators::Pair;
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::{input, Answer, ParseError, Rng, Solver};
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input = Vec<Vec<CalcPart>>;
+
+    fn id(&self) -> u32 {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Operation Order"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_homework(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        input.iter().map(|line| eval_parts(line)).sum::<i64>().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        input.iter().map(|line| eval_parts_priority(line)).sum::<i64>().into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // No more than ten numbers a line, so even all multiplied together they fit.
+        let mut homework = String::new();
+        for _ in 0..scale.unwrap_or(370) {
+            let leaves = 2 + rng.below(9);
+            homework.push_str(&expression(rng, leaves));
+            homework.push('\n');
+        }
+        Some(homework)
+    }
+}
+
+/// One piece of an expression.
+#[derive(Debug, PartialEq, Clone)]
+pub enum CalcPart {
+    Num(i64),
+    /// A bracketed expression.
+    Group(Vec<CalcPart>),
+    Plus,
+    Times,
+}
+
+/// Every line of homework, evaluated left to right.
+pub fn eval_file(data: &str) -> Result<i64, ParseError> {
+    Ok(parse_homework(data)?.iter().map(|line| eval_parts(line)).sum())
+}
+
+/// Every line of homework, with `+` evaluated before `*`.
+pub fn eval_file_priority(data: &str) -> Result<i64, ParseError> {
+    Ok(parse_homework(data)?.iter().map(|line| eval_parts_priority(line)).sum())
+}
+
+/// Makes up an expression with `leaves` single digit numbers, some of them grouped in brackets.
+fn expression(rng: &mut Rng, leaves: usize) -> String {
+    let mut sizes = vec![1; 2 + rng.below(leaves.min(6) - 1)];
+    for _ in sizes.len()..leaves {
+        let i = rng.below(sizes.len());
+        sizes[i] += 1;
+    }
+
+    let mut code = String::new();
+    for (i, size) in sizes.into_iter().enumerate() {
+        if i > 0 {
+            code.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+        if size == 1 {
+            code.push_str(&rng.between(1, 9).to_string());
+        } else {
+            write!(code, "({})", expression(rng, size)).unwrap();
+        }
+    }
+    code
+}
+
+/// Every non-blank line of homework as an expression.
+pub fn parse_homework(data: &str) -> Result<Vec<Vec<CalcPart>>, ParseError> {
+    let mut lines = vec![];
+    for (n, line) in input::lines(data) {
+        lines.push(parse_calc(line).map_err(|e| e.on_line(n))?);
+    }
+    Ok(lines)
+}
+
+/// Parses a single expression, which must be the whole of `code`.
+pub fn parse_calc(code: &str) -> Result<Vec<CalcPart>, ParseError> {
+    let calc = DParser::parse(Rule::line, code)
+        .map_err(|e| ParseError::from_pest(18, code, &e))?
+        .next()
+        .unwrap();
+    Ok(to_parts(calc))
+}
+
+fn to_parts(calc: Pair<'_, Rule>) -> Vec<CalcPart> {
+    calc.into_inner()
+        .map(|part| match part.as_rule() {
+            Rule::num => CalcPart::Num(part.as_str().parse().unwrap()),
+            Rule::calc => CalcPart::Group(to_parts(part)),
+            Rule::plus => CalcPart::Plus,
+            Rule::times => CalcPart::Times,
+            _ => panic!("unexpected match"),
+        })
+        .collect()
+}
+
+pub fn eval_priority(code: &str) -> Result<i64, ParseError> {
+    Ok(eval_parts_priority(&parse_calc(code)?))
+}
+
+/// Evaluates an expression with `+` before `*`.
+pub fn eval_parts_priority(parts: &[CalcPart]) -> i64 {
+    // In priotity mode, we evaluate a list of op/values, then can apply "+" before "-"
+    let mut calc_parts: Vec<CalcPart> = parts
+        .iter()
+        .map(|part| match part {
+            CalcPart::Group(inner) => CalcPart::Num(eval_parts_priority(inner)),
+            atom => atom.clone(),
+        })
+        .collect();
+
+    // Now reduce '+'
+    loop {
+        let mut changes = 0;
+        let mut index = 0;
+        let mut reduced_plus = vec![];
+    loop {
+        if index + 2 >= calc_parts.len() {
+            if index < calc_parts.len() {
+                reduced_plus.push(calc_parts[index].clone());
+                if index + 1 < calc_parts.len() {
+                    reduced_plus.push(calc_parts[index + 1].clone());
+                    changes += 1;
+                }
+            }
+            break;
+        }
+
+        if calc_parts[index + 1] == CalcPart::Plus {
+            if let CalcPart::Num(lhs) = calc_parts[index]  {
+                if let CalcPart::Num(rhs) = calc_parts[index + 2]  {
+                    reduced_plus.push(CalcPart::Num(lhs + rhs));
+                    index += 3;
+                } else {
+                    panic!("Expected number.");
+                }
+            } else {
+                panic!("Expected number.");
+            }
+        } else {
+            reduced_plus.push(calc_parts[index].clone());
+            reduced_plus.push(calc_parts[index + 1].clone());
+            index += 2;
+        }
+    }
+        calc_parts = reduced_plus;
+        if changes == 0 {
+            break;
+        }
+    }
+
+    // We *should* just have multiples left, so can just multiply all remaining numbers.
+    let mut product = 1;
+    for cp in calc_parts {
+        if let CalcPart::Num(num) = cp {
+            product *= num;
+        }
+    }
+
+    product
+}
+
+
+pub fn eval(code: &str) -> Result<i64, ParseError> {
+    Ok(eval_parts(&parse_calc(code)?))
+}
+
+/// Evaluates an expression left to right.
+pub fn eval_parts(parts: &[CalcPart]) -> i64 {
+    let mut running: Option<i64> = None;
+    let mut current_op: Option<char> = None;
+
+    for part in parts {
+        let val = match part {
+            CalcPart::Num(val) => *val,
+            CalcPart::Group(inner) => eval_parts(inner),
+            CalcPart::Plus => {
+                current_op = Some('+');
+                continue;
+            }
+            CalcPart::Times => {
+                current_op = Some('*');
+                continue;
+            }
+        };
+        match running {
+            None => { running = Some(val); },
+            Some(r) => {
+                // We have an existing value, so apply the current op.
+                match current_op {
+                    Some('+') => { running = Some(r + val); }
+                    Some('*') => { running = Some(r * val); }
+                    _ => panic!("Attempt to apply non-op"),
+                }
+            }
+        }
+    }
+
+    match running {
+        Some(v) => v,
+        None => panic!("Could not evaluate.")
+    }
+}
+
+#[derive(Parser)]
+#[grammar = "parsers/day18.pest"]
+pub struct DParser {}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data18.txt")
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(51), eval("1 + (2 * 3) + (4 * (5 + 6))"));
+
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(231), eval_priority("1 + 2 * 3 + 4 * 5 + 6"));
+        assert_eq!(Ok(23340), eval_priority("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"));
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(Ok(1_408_133_923_393), eval_file(data()));
+        assert_eq!(Ok(314_455_761_823_725), eval_file_priority(data()));
+    }
+
+    #[test]
+    fn test_bad() {
+        let err = eval_file("1 + 2\n3 * (4 + x)\n").unwrap_err();
+        assert_eq!((2, 10), (err.line, err.col));
+        assert_eq!("3 * (4 + x)", err.text);
+
+        let err = eval("1 + 2 3").unwrap_err();
+        assert_eq!((1, 7), (err.line, err.col));
+    }
+}
@@ -2,29 +2,37 @@ use pest::Parser;
 use pest_derive::Parser;
 
 use std::collections::HashMap;
-use std::time::SystemTime;
 
-use advent2020::{fmt_bright, print_day, print_duration};
+use crate::{debug, warn, Answer, ParseError, Rng, Solver};
 
-fn data() -> &'static str {
-    include_str!("../data/data19.txt")
-}
+pub struct Day19;
 
-pub fn run() {
-    print_day(19);
+impl Solver for Day19 {
+    type Input = Transmission;
 
-    let start = SystemTime::now();
+    fn id(&self) -> u32 {
+        19
+    }
 
-    let (match_count, extended_match_count) = parse(data());
+    fn title(&self) -> &'static str {
+        "Monster Messages"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
 
-    // Let's do this...
+    fn part1(&self, input: &Self::Input) -> Answer {
+        matched(input).into()
+    }
 
-    let timed = SystemTime::now().duration_since(start).unwrap();
+    fn part2(&self, input: &Self::Input) -> Answer {
+        matched_with_loops(input).into()
+    }
 
-    println!("Found {} matches.", fmt_bright(&match_count));
-    println!("Found {} extended matches.", fmt_bright(&extended_match_count));
-    
-    print_duration(timed);
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        Some(generate_transmission(rng, scale.unwrap_or(400)))
+    }
 }
 
 #[derive(Debug)]
@@ -57,7 +65,7 @@ impl Rules {
     fn matches(&self, index: usize, text: &str, trying42: usize, trying11: usize) -> Option<(String, String)> {
         if index == 8 {
             // We greedily match rule 42
-            let mut matched = String::from("");
+            let mut matched = String::new();
             let mut remainder = String::from(text);
             let mut ncount = 0;
             loop {
@@ -80,7 +88,7 @@ impl Rules {
         } else if index == 11 {
             // Can look like 42{n} 31{n} where n >= 1;
             // Collect 42s
-            let mut matched = String::from("");
+            let mut matched = String::new();
             let mut remainder = String::from(text);
             let mut n = 0;
             loop {
@@ -114,11 +122,23 @@ impl Rules {
         }
     }
 
+    /// Some text that `index` matches, picking options at random.
+    fn sample(&self, index: usize, rng: &mut Rng) -> String {
+        let rule = &self.rules[&index];
+        rule.options[rng.below(rule.options.len())]
+            .iter()
+            .map(|frag| match frag {
+                RuleFragment::Literal(c) => c.to_string(),
+                RuleFragment::Ref(i) => self.sample(*i, rng),
+            })
+            .collect()
+    }
+
     fn inner_matches(&self, index: usize, text: &str, trying42: usize, trying11: usize) -> Option<(String, String)> {
         if let Some(r) = self.rules.get(&index) {
             for option in &r.options {
                 // We attempt to match each option in turn.
-                let mut matched = String::from("");
+                let mut matched = String::new();
                 let mut remainder = String::from(text);
                 let mut failed = false;
                 for frag in option {
@@ -147,7 +167,7 @@ impl Rules {
                                 break;
                             }
                         },
-                    };
+                    }
                 }
                 if !failed {
                     // We managed to match against this option!
@@ -162,9 +182,95 @@ impl Rules {
 
 
 
-pub fn parse(transmission: &str) -> (usize, usize) {
+/// Makes up rules and `messages` messages to check against them.
+///
+/// Rules 42 and 31 are the tops of a tree of rules which only ever match eight letters, with every option the same
+/// length. That's what the matcher relies on, as it takes the first option that matches rather than trying them all.
+/// About half the messages are made by the rules, as rule 0 with 8 and 11 looping, and the rest are just letters.
+pub fn generate_transmission(rng: &mut Rng, messages: usize) -> String {
+    let mut ids: Vec<usize> = (1..200).filter(|id| ![8, 11, 31, 42].contains(id)).collect();
+    rng.shuffle(&mut ids);
+    let mut rules: HashMap<usize, RuleOptions> = HashMap::new();
+
+    let (a, b) = (ids.pop().unwrap(), ids.pop().unwrap());
+    rules.insert(a, RuleOptions { options: vec![vec![RuleFragment::Literal('a')]] });
+    rules.insert(b, RuleOptions { options: vec![vec![RuleFragment::Literal('b')]] });
+
+    // Each level matches twice as many letters as the one below.
+    let mut level = vec![a, b];
+    for depth in 1..=3 {
+        let width = if depth == 3 { 2 } else { 4 };
+        let mut next = vec![];
+        for i in 0..width {
+            let id = if depth < 3 { ids.pop().unwrap() } else if i == 0 { 42 } else { 31 };
+            let options = (0..rng.between(1, 2))
+                .map(|_| vec![RuleFragment::Ref(*rng.pick(&level)), RuleFragment::Ref(*rng.pick(&level))])
+                .collect();
+            rules.insert(id, RuleOptions { options });
+            next.push(id);
+        }
+        level = next;
+    }
+    rules.insert(0, RuleOptions { options: vec![vec![RuleFragment::Ref(8), RuleFragment::Ref(11)]] });
+    rules.insert(8, RuleOptions { options: vec![vec![RuleFragment::Ref(42)]] });
+    rules.insert(11, RuleOptions { options: vec![vec![RuleFragment::Ref(42), RuleFragment::Ref(31)]] });
+
+    let mut lines: Vec<String> = rules
+        .iter()
+        .map(|(id, rule)| {
+            let options: Vec<String> = rule
+                .options
+                .iter()
+                .map(|seq| {
+                    let parts: Vec<String> = seq
+                        .iter()
+                        .map(|frag| match frag {
+                            RuleFragment::Ref(i) => i.to_string(),
+                            RuleFragment::Literal(c) => format!("\"{}\"", c),
+                        })
+                        .collect();
+                    parts.join(" ")
+                })
+                .collect();
+            format!("{}: {}", id, options.join(" | "))
+        })
+        .collect();
+    // Sorted first, as the hash map's order changes from run to run and the same seed should give the same rules.
+    lines.sort_unstable();
+    rng.shuffle(&mut lines);
+    lines.push(String::new());
+
+    let rules = Rules { rules };
+    for _ in 0..messages {
+        let message = if rng.chance(0.5) {
+            let front = rng.between(2, 5);
+            let back = rng.between(1, front - 1);
+            let mut message = String::new();
+            for _ in 0..front {
+                message.push_str(&rules.sample(42, rng));
+            }
+            for _ in 0..back {
+                message.push_str(&rules.sample(31, rng));
+            }
+            message
+        } else {
+            (0..8 * rng.between(3, 6)).map(|_| if rng.chance(0.5) { 'a' } else { 'b' }).collect()
+        };
+        lines.push(message);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// The rules and the messages received.
+#[derive(Debug)]
+pub struct Transmission {
+    rules: Rules,
+    messages: Vec<String>,
+}
+
+pub fn parse(transmission: &str) -> Result<Transmission, ParseError> {
     let file = DParser::parse(Rule::file, transmission)
-        .expect("unsuccesful parse")
+        .map_err(|e| ParseError::from_pest(19, transmission, &e))?
         .next()
         .unwrap();
 
@@ -173,7 +279,7 @@ pub fn parse(transmission: &str) -> (usize, usize) {
 
     for line in file.into_inner() {
         match line.as_rule() {
-            Rule::datum => { data.push(line.as_str()); },
+            Rule::datum => { data.push(line.as_str().to_string()); },
             Rule::rule => {
                 // We're reading a rule.
                 let mut index: Option<usize> = None;
@@ -209,23 +315,35 @@ pub fn parse(transmission: &str) -> (usize, usize) {
             // Silently consume EOI.
             Rule::EOI => {},
             _ => {
-                println!("Unexpected rule: {:?} {}", line.as_rule(), line.as_str());
+                warn!("Unexpected rule: {:?} {}", line.as_rule(), line.as_str());
                 panic!("Unexpected rule");
             },
-        };
+        }
     };
 
-    println!("Read {} rules, and {} data.", rules.rules.len(), data.len());
+    debug!("Read {} rules, and {} data.", rules.rules.len(), data.len());
+    Ok(Transmission { rules, messages: data })
+}
 
+/// How many messages match rule 0 with the rules as given.
+pub fn matched(transmission: &Transmission) -> usize {
+    let rules = &transmission.rules;
     let mut match_count = 0;
-    let mut extended_match_count = 0;
-    for datum in data {
+    for datum in &transmission.messages {
         if let Some((_matched, remainder)) = rules.matches(0, datum, 1, 1) {
             if remainder.is_empty() {
                 match_count += 1;
             }
         }
-        
+    }
+    match_count
+}
+
+/// How many messages match rule 0 with rules 8 and 11 replaced by their looping versions.
+pub fn matched_with_loops(transmission: &Transmission) -> usize {
+    let rules = &transmission.rules;
+    let mut extended_match_count = 0;
+    for datum in &transmission.messages {
         let mut full_match = false;
         for trying42 in 1..10 {
             if full_match {
@@ -246,18 +364,27 @@ pub fn parse(transmission: &str) -> (usize, usize) {
             }
         }
     }
-
-    (match_count, extended_match_count)
-} 
+    extended_match_count
+}
 
 #[derive(Parser)]
 #[grammar = "parsers/day19.pest"]
 pub struct DParser {}
 
+#[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    fn data() -> &'static str {
+        include_str!("../../data/data19.txt")
+    }
+
+    fn matches(data: &str) -> (usize, usize) {
+        let transmission = parse(data).unwrap();
+        (matched(&transmission), matched_with_loops(&transmission))
+    }
+
     #[test]
     fn test_small1() {
         let data_small = "0: 4 1 5
@@ -272,17 +399,17 @@ bababa
 abbbab
 aaabbb
 aaaabbb";
-        assert_eq!((2,2), parse(&data_small));
+        assert_eq!((2, 2), matches(data_small));
     }
 
     #[test]
     fn test_small2() {
-        let data_small = include_str!("../data/data19_small2.txt");
-        assert_eq!((3, 12), parse(&data_small));
+        let data_small = include_str!("../../data/data19_small2.txt");
+        assert_eq!((3, 12), matches(data_small));
     }
 
     #[test]
     fn test_all() {
-        assert_eq!((120, 350), parse(data()));
+        assert_eq!((120, 350), matches(data()));
     }
 }
@@ -0,0 +1,162 @@
+use pest_derive::Parser;
+use pest::Parser;
+use std::string::String;
+use std::fmt::Write;
+
+use crate::{Answer, ParseError, Rng, Solver};
+
+#[derive(Parser)]
+#[grammar = "parsers/day2.pest"]
+pub struct DParser {}
+
+/// A password with the policy it was set under, e.g. `1-3 a: abcde`.
+#[derive(Debug, PartialEq)]
+pub struct RuledPassword {
+    pub min: usize,
+    pub max: usize,
+    pub letter: char,
+    pub password: String,
+}
+
+impl RuledPassword {
+    /// The letter appears between min and max times (the sled rental policy).
+    pub fn valid(&self) -> bool {
+        let count = self.password.chars().filter(|x| *x == self.letter).count();
+        count >= self.min && count <= self.max
+    }
+
+    /// The letter is at exactly one of the two positions (the toboggan policy).
+    pub fn valid2(&self) -> bool {
+        let chars: Vec<char> = self.password.chars().collect();
+        let got_min = match chars.get(self.min - 1) {
+            Some(x) => self.letter == *x,
+            None => false,
+        };
+        let got_max = match chars.get(self.max - 1) {
+            Some(x) => self.letter == *x,
+            None => false,
+        };
+
+        !(got_min && got_max) && (got_min || got_max)
+    }
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<RuledPassword>;
+
+    fn id(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Password Philosophy"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_file(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        valid_by_count(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        valid_by_position(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut lines = String::new();
+        for _ in 0..scale.unwrap_or(1000) {
+            let len = rng.between(3, 20);
+            let min = rng.between(1, len - 1);
+            let max = rng.between(min + 1, len);
+            let letter = rng.letter();
+            // Favour the policy's letter, so a fair share of the passwords pass.
+            let password: String = (0..len)
+                .map(|_| if rng.chance(0.4) { letter } else { rng.letter() })
+                .collect();
+            writeln!(lines, "{}-{} {}: {}", min, max, letter, password).unwrap();
+        }
+        Some(lines)
+    }
+}
+
+/// How many passwords pass the sled rental policy.
+pub fn valid_by_count(passwords: &[RuledPassword]) -> usize {
+    passwords.iter().filter(|p| p.valid()).count()
+}
+
+/// How many passwords pass the toboggan policy.
+pub fn valid_by_position(passwords: &[RuledPassword]) -> usize {
+    passwords.iter().filter(|p| p.valid2()).count()
+}
+
+pub fn parse_file(unparsed_file: &str) -> Result<Vec<RuledPassword>, ParseError> {
+    let file = DParser::parse(Rule::file, unparsed_file)
+        .map_err(|e| ParseError::from_pest(2, unparsed_file, &e))?
+        .next()
+        .unwrap();
+
+    let mut passwords = vec![];
+    for line in file.into_inner() {
+        if let Rule::rule = line.as_rule() {
+            let mut inner = line.into_inner();
+            let mut range_inner = inner.next().unwrap().into_inner();
+            // The grammar only lets digits through, but they could still be too big.
+            let mut bound = || {
+                let pair = range_inner.next().unwrap();
+                pair.as_str()
+                    .parse::<usize>()
+                    .map_err(|_| ParseError::at(2, unparsed_file, pair.as_span().start(), "a smaller number"))
+            };
+            let min = bound()?;
+            let max = bound()?;
+
+            let letter = inner.next().unwrap().into_inner().next().unwrap();
+            let letter = letter.as_str().chars().next().unwrap();
+
+            let password = inner.next().unwrap().as_str().to_string();
+
+            passwords.push(RuledPassword {
+                min,
+                max,
+                letter,
+                password,
+            });
+        }
+    }
+    Ok(passwords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(data: &str) -> (usize, usize, usize) {
+        let passwords = parse_file(data).unwrap();
+        (passwords.len(), valid_by_count(&passwords), valid_by_position(&passwords))
+    }
+
+    #[test]
+    fn part12_test() {
+        assert_eq!((1, 1, 1), report("1-3 a: abcde"));
+        assert_eq!((1, 0, 0), report("1-3 b: cdefg"));
+        assert_eq!((1, 1, 0), report("2-9 c: ccccccccc"));
+    }
+
+    #[test]
+    fn all_test() {
+        let cbytes = include_bytes!("../../data/data2.txt");
+        let contents = String::from_utf8_lossy(cbytes);
+        assert_eq!((1000, 538, 489), report(&contents));
+    }
+
+    #[test]
+    fn bad_test() {
+        let err = parse_file("1-3 a: abcde\n1-x b: cdefg\n").unwrap_err();
+        assert_eq!((2, 2, 3), (err.day, err.line, err.col));
+        assert_eq!("1-x b: cdefg", err.text);
+    }
+}
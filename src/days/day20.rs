@@ -0,0 +1,598 @@
+#![allow(clippy::redundant_closure)]
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::fmt::Write;
+
+use crate::grid::Cell;
+use crate::{debug, input, trace, Answer, Grid2D, ParseError, Rng, Solver};
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input = Jigsaw;
+
+    fn id(&self) -> u32 {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Jurassic Jigsaw"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_tiles(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        corner_product(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        turbulence(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        Some(generate_tiles(rng, scale.unwrap_or(12)))
+    }
+
+    fn visualise(&self, input: &Self::Input) -> Option<String> {
+        Some(chart_with_monsters(input))
+    }
+}
+
+/// The tiles, each with the number of the line that names it.
+fn tiles_from_file(data: &str) -> Result<Vec<(usize, Tile)>, ParseError> {
+    let mut tiles = vec![];
+    let mut scan = vec![];
+    let mut tile_index = 0;
+    let mut tile_line = 0;
+    for (n, line) in input::lines(data) {
+        if let Some(rest) = line.strip_prefix("Tile ") {
+            if !scan.is_empty() {
+                return Err(ParseError::at_line(20, data, n, 1, "10 rows of pixels"));
+            }
+            // The line looks like "Tile 2311:".
+            tile_index = rest
+                .strip_suffix(':')
+                .and_then(|idx| idx.trim().parse().ok())
+                .ok_or_else(|| ParseError::at_line(20, data, n, 6, "a tile number and a colon"))?;
+            tile_line = n;
+            scan = vec![];
+        } else {
+            // This should be a scan line:
+            let mut scan_line = vec![];
+            for (i, c) in line.chars().enumerate() {
+                scan_line.push(bool::from_char(c).ok_or_else(|| ParseError::at_line(20, data, n, i + 1, bool::EXPECTED))?);
+            }
+            if scan_line.len() != 10 {
+                return Err(ParseError::at_line(20, data, n, scan_line.len().min(10) + 1, "a row of 10 pixels"));
+            }
+            scan.push(scan_line);
+            if scan.len() == 10 {
+                // Have completed a tile.
+                tiles.push((tile_line, Tile::new(tile_index, Grid2D::from_rows(scan))));
+                scan = vec![];
+            }
+        }
+    }
+    if !scan.is_empty() {
+        return Err(ParseError::at(20, data, data.len(), "10 rows of pixels"));
+    }
+    Ok(tiles)
+}
+
+fn map_tiles(tiles: &[Tile]) -> HashMap<u16, Vec<(u16, u8)>> {
+    // Store the key/edge/orientation triples for every number.
+    let mut lookup: HashMap<u16, Vec<(u16, u8)>> = HashMap::new();
+    for tile in tiles {
+        for e in 0..4 {
+            let k_natural = tile.key(e, 0);
+            lookup.entry(k_natural).or_insert_with(|| vec![]).push((tile.index, e));
+
+            let k_twisted = tile.key(e, 2);
+            lookup.entry(k_twisted).or_insert_with(|| vec![]).push((tile.index, e));
+        }
+    }
+    lookup
+}
+
+/// The jigsaw's tiles, and which tiles have each edge.
+#[derive(Debug)]
+pub struct Jigsaw {
+    tiles: Vec<Tile>,
+    lookup: HashMap<u16, Vec<(u16, u8)>>,
+}
+
+pub fn parse_tiles(data: &str) -> Result<Jigsaw, ParseError> {
+    let (lines, tiles): (Vec<usize>, Vec<Tile>) = tiles_from_file(data)?.into_iter().unzip();
+    debug!("Read {} tiles:", tiles.len());
+    if tiles.is_empty() {
+        return Err(ParseError::at(20, data, data.len(), "a tile"));
+    }
+    let lookup = map_tiles(&tiles);
+
+    // Every tile needs a neighbour on at least two sides, and a corner's two must be next to each other, not opposite.
+    let mut corners = 0;
+    for (tile, &n) in tiles.iter().zip(&lines) {
+        let matched = matched_edges(tile, &lookup);
+        match matched.iter().filter(|&&m| m).count() {
+            0 | 1 => return Err(ParseError::at_line(20, data, n, 1, "a tile that fits the jigsaw")),
+            2 if corner_orientation(matched).is_none() => {
+                return Err(ParseError::at_line(20, data, n, 1, "a tile that fits the jigsaw"));
+            }
+            2 => corners += 1,
+            _ => {}
+        }
+    }
+    if corners != 4 {
+        return Err(ParseError::at(20, data, data.len(), "tiles that make a rectangle with four corners"));
+    }
+    Ok(Jigsaw { tiles, lookup })
+}
+
+/// Which of the tile's edges, as it comes, match an edge of some other tile.
+fn matched_edges(tile: &Tile, lookup: &HashMap<u16, Vec<(u16, u8)>>) -> [bool; 4] {
+    let mut matched = [false; 4];
+    for (e, m) in (0..4).zip(matched.iter_mut()) {
+        // An edge that reads the same both ways round is in the lookup twice for its own tile.
+        let key = tile.key(e, 0);
+        *m = lookup.get(&key).is_some_and(|matches| matches.iter().any(|&(index, _)| index != tile.index));
+    }
+    matched
+}
+
+/// How far to turn a corner tile so its two matched edges face right and down, if they're next to each other.
+fn corner_orientation(matched: [bool; 4]) -> Option<u8> {
+    match matched {
+        [false, true, true, false] => Some(0),
+        [false, false, true, true] => Some(1),
+        [true, false, false, true] => Some(2),
+        [true, true, false, false] => Some(3),
+        _ => None,
+    }
+}
+
+/// The tiles with only two edges that match another tile, each turned so those edges face right and down.
+fn find_corners(jigsaw: &Jigsaw) -> Vec<Oriented> {
+    let Jigsaw { tiles, lookup } = jigsaw;
+    let mut oriented_corners: Vec<Oriented> = vec![];
+
+    // Now lets look at all the tiles, and see which have mathcing edges. `parse_tiles` has checked each corner's
+    // matched edges are next to each other.
+    for tile in tiles {
+        let matched = matched_edges(tile, lookup);
+        if matched.iter().filter(|&&m| m).count() == 2 {
+            if let Some(orientation) = corner_orientation(matched) {
+                oriented_corners.push(Oriented::new(orientation, tile));
+            }
+        }
+    }
+
+    oriented_corners
+}
+
+/// The product of the ids of the four corner tiles.
+pub fn corner_product(jigsaw: &Jigsaw) -> u64 {
+    let corners: Vec<u64> = find_corners(jigsaw).iter().map(|c| c.tile.index.into()).collect();
+    let corner_product = corners.iter().product::<u64>();
+    debug!("Found potential corners {:?} . Prod = {}", corners, corner_product);
+    corner_product
+}
+
+/// The rough water (`#`) that isn't part of a sea monster, once the image is put back together.
+pub fn turbulence(jigsaw: &Jigsaw) -> u64 {
+    turbulence_for(&assemble(jigsaw))
+}
+
+/// The image put back together, with every sea monster drawn in `O`.
+pub fn chart_with_monsters(jigsaw: &Jigsaw) -> String {
+    let chart = assemble(jigsaw);
+    let mut drawn: Vec<Vec<char>> = chart.iter().map(|row| row.chars().collect()).collect();
+    for ri in 0..chart.len() {
+        for ci in 0..chart[ri].len() {
+            if monster_at(&chart, ri, ci) {
+                for (dr, monster_row) in MONSTER.iter().enumerate() {
+                    for (dc, _) in monster_row.match_indices('#') {
+                        drawn[ri + dr][ci + dc] = 'O';
+                    }
+                }
+            }
+        }
+    }
+    drawn.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+/// The tiles fitted together with their borders taken off, as rows of `#` and `.`.
+fn assemble(jigsaw: &Jigsaw) -> Vec<String> {
+    let lookup = &jigsaw.lookup;
+    let tile_map: HashMap<u16, &Tile> = jigsaw.tiles.iter().map(|tile| (tile.index, tile)).collect();
+    let oriented_corners = find_corners(jigsaw);
+
+    // ! Lets build the image array!
+    let mut init: Option<&Oriented> = None;
+    let mut anchor_tile = oriented_corners.first().unwrap().copy();
+    let mut combined: Vec<Vec<Oriented>> = vec![];
+    // Loop on rows. No row or column can hold more than every tile, however the edges happen to match up.
+    while combined.len() < jigsaw.tiles.len() {
+        // Find the first tile in the row.
+        if init.is_none() {
+            // Pull first from one of the corners.
+            let first_corner = oriented_corners.first().unwrap();
+            init = Some(first_corner);
+
+        }  else if let Some(next_below) = match_tile_to(lookup, &tile_map, anchor_tile.tile.index, anchor_tile.edge_key(2), 0) { 
+                    anchor_tile = next_below;
+                    init = Some(&anchor_tile);
+        } else {
+            // We do not have a matching tile.
+            break;
+        }
+
+        let mut current = Oriented::new(anchor_tile.orientation, &anchor_tile.tile);
+        let mut row = vec![Oriented::new(anchor_tile.orientation, &anchor_tile.tile)];
+
+        // Work over the row from the left anchor tile.
+        while row.len() < jigsaw.tiles.len() {
+            let Some(next_oriented) = match_tile_to(lookup, &tile_map, current.tile.index, current.edge_key(1), 3) else {
+                break;
+            };
+            current = next_oriented.copy();
+            row.push(next_oriented);
+        }
+        combined.push(row);
+    }
+
+    let mut chart: Vec<String> = vec![];
+    trace!("Re-assembled chart:");
+    trace!();
+    for row in combined {
+        let mut labels = String::new();
+        for t in &row {
+            write!(labels, " {}/{} ", t.tile.index, t.orientation).unwrap();
+        }
+        let images: Vec<Grid2D<bool>> = row.iter().map(Oriented::image).collect();
+        for ri in 0..8 {
+            chart.push(images.iter().flat_map(|image| image.rows().nth(ri).unwrap()).map(Cell::to_char).collect());
+        }
+        trace!("{}", labels);
+    }
+    chart
+}
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// Makes up a jigsaw of `side` by `side` tiles, each turned and flipped at random, whose image hides some sea monsters.
+///
+/// Every edge is different from every other, either way round, so there's only one way to fit the tiles together. Ten
+/// pixels don't allow for all that many different edges, so `side` is at most 12, and it's at least 3 so the image is
+/// wide enough for a monster.
+pub fn generate_tiles(rng: &mut Rng, side: usize) -> String {
+    let side = side.clamp(3, 12);
+    let mut grid = loop {
+        if let Some(grid) = tile_borders(rng, side) {
+            break grid;
+        }
+    };
+
+    // The image, once the borders are taken off, with the monsters placed so they don't overlap.
+    let width = 8 * side;
+    let mut image: Vec<Vec<bool>> = (0..width).map(|_| (0..width).map(|_| rng.chance(0.3)).collect()).collect();
+    let mut taken = vec![vec![false; width]; width];
+    let monster: Vec<(usize, usize)> = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| row.char_indices().filter(|&(_, c)| c == '#').map(move |(x, _)| (y, x)))
+        .collect();
+    for _ in 0..side * side / 3 {
+        let (y0, x0) = (rng.below(width - 2), rng.below(width - 19));
+        if monster.iter().all(|&(y, x)| !taken[y0 + y][x0 + x]) {
+            for &(y, x) in &monster {
+                image[y0 + y][x0 + x] = true;
+                taken[y0 + y][x0 + x] = true;
+            }
+        }
+    }
+    for (y, row) in image.iter().enumerate() {
+        for (x, &pixel) in row.iter().enumerate() {
+            grid[9 * (y / 8) + 1 + y % 8][9 * (x / 8) + 1 + x % 8] = pixel;
+        }
+    }
+
+    let mut ids: Vec<usize> = (1000..10000).collect();
+    rng.shuffle(&mut ids);
+    let mut tiles = vec![];
+    for r in 0..side {
+        for c in 0..side {
+            let mut tile: Vec<Vec<bool>> = grid[9 * r..9 * r + 10].iter().map(|row| row[9 * c..9 * c + 10].to_vec()).collect();
+            for _ in 0..rng.below(4) {
+                tile = (0..10).map(|i| (0..10).map(|j| tile[9 - j][i]).collect()).collect();
+            }
+            if rng.chance(0.5) {
+                tile.reverse();
+            }
+            let mut text = format!("Tile {}:\n", ids[tiles.len()]);
+            for row in tile {
+                text.extend(row.iter().map(|&p| if p { '#' } else { '.' }));
+                text.push('\n');
+            }
+            tiles.push(text);
+        }
+    }
+    rng.shuffle(&mut tiles);
+    tiles.join("\n")
+}
+
+/// The pixels of every tile laid out together, each tile overlapping its neighbours by the edge they share, with only
+/// the borders filled in. Gives up with `None` if it runs out of different edges.
+fn tile_borders(rng: &mut Rng, side: usize) -> Option<Vec<Vec<bool>>> {
+    let pixels = 9 * side + 1;
+    let mut grid = vec![vec![false; pixels]; pixels];
+    // The corners are shared by up to four edges, so they're settled first.
+    for r in (0..pixels).step_by(9) {
+        for c in (0..pixels).step_by(9) {
+            grid[r][c] = rng.chance(0.5);
+        }
+    }
+
+    let mut used = HashSet::new();
+    for i in (0..pixels).step_by(9) {
+        for j in (0..pixels - 1).step_by(9) {
+            for pixel in [|i, j, k| (i, j + k), |i, j, k| (j + k, i)] {
+                let mut tries = 0;
+                loop {
+                    for k in 1..9 {
+                        let (y, x) = pixel(i, j, k);
+                        grid[y][x] = rng.chance(0.5);
+                    }
+                    let edge: Vec<bool> = (0..10)
+                        .map(|k| {
+                            let (y, x) = pixel(i, j, k);
+                            grid[y][x]
+                        })
+                        .collect();
+                    let mut reversed = edge.clone();
+                    reversed.reverse();
+                    let (key, reversed) = (Tile::key_from_vec(&edge), Tile::key_from_vec(&reversed));
+                    if key != reversed && used.insert(key.min(reversed)) {
+                        break;
+                    }
+                    tries += 1;
+                    if tries > 1000 {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+    Some(grid)
+}
+
+fn match_tile_to(lookup: &HashMap<u16, Vec<(u16, u8)>>, tile_map: &HashMap<u16, &Tile>, from_index: u16, from_key: u16, to_edge: u8)
+                 -> Option<Oriented> {
+    if let Some(matches) = lookup.get(&from_key) {
+        if let Some((t_index, _)) = matches.iter().find(|(t_index, _)| *t_index != from_index) {
+            // We've got the tile and edge.
+            let tile = tile_map.get(t_index).unwrap();
+            let try_first = Oriented::new((to_edge + 3) % 4, tile);
+            if try_first.edge_key(to_edge) == from_key {
+                return Some(try_first);
+            }
+            // Find the orientation and flip.
+            for orientation in 0..4 {
+                let try_tile = Oriented::new(orientation, tile);
+                if try_tile.edge_key(to_edge) == from_key {
+                    return Some(try_tile);
+                }
+                let try_flipped = try_tile.flipped();
+                if try_flipped.edge_key(to_edge) == from_key {
+                    return Some(try_flipped);
+                }
+            }
+        }
+    }
+    None
+    
+}
+
+fn turbulence_for(chart: &[String]) -> u64 {
+    let mut hash_count: u64 = 0;
+    let mut monster_count = 0;
+    for (ri, chart_row) in chart.iter().enumerate() {
+        for ci in 0..chart_row.len() {
+            if chart_row.get(ci..=ci) == Some("#") {
+                hash_count += 1;
+            }
+            if monster_at(chart, ri, ci) {
+                monster_count += 1;
+            }
+        }
+    }
+
+    // Monsters can overlap in a made-up image, sharing some of their pixels.
+    let turbulence = hash_count.saturating_sub(15 * monster_count);
+    debug!("Saw {} waves and {} monsters. Turbulence = {}", hash_count, monster_count, turbulence);
+    turbulence
+}
+
+//                   # 
+// #    ##    ##    ###
+//  #  #  #  #  #  #   
+fn monster_at(chart: &[String], row: usize, col: usize) -> bool {
+    let monster_coords1: Vec<usize> = vec![18];
+    let monster_coords2 = vec![0, 5, 6, 11, 12, 17, 18, 19];
+    let monster_coords3 = vec![1, 4, 7, 10, 13, 16];
+
+    let monster_coords = [monster_coords1, monster_coords2, monster_coords3];
+
+    // Check the array is big enough.
+    if chart.len() <= row + 2 {
+        return false;
+    }
+    
+    if chart[row..=row + 2].iter().any(|chart_row| chart_row.len() <= col + 19) {
+        return false;
+    }
+
+    for (row_delta, monster_row) in monster_coords.iter().enumerate() {
+        let row_chars = chart.get(row + row_delta).unwrap();
+        for monster_col in monster_row {
+            let check_col = monster_col + col;
+            if row_chars.get(check_col..=check_col) != Some("#") {
+                return false;
+            }
+        }
+    } 
+
+    true
+}
+
+#[derive(Debug)]
+struct Oriented {
+    tile: Tile,
+    orientation: u8
+}
+
+impl Oriented {
+    fn new(orientation: u8, tile: &Tile) -> Self {
+        Self{orientation, tile: Tile { index:tile.index, scans: tile.scans.clone()}}
+    }
+
+    fn copy(&self) -> Self {
+        Self::new(self.orientation, &self.tile)
+    }
+
+    fn edge_key(&self, e: u8) -> u16 {
+        self.tile.key(e, self.orientation)
+    }
+
+    fn flipped(&self) -> Self {
+        Self{
+            orientation: self.orientation,
+            tile: self.tile.flipped()
+        }
+    }
+
+    /// The tile as it sits in the image, turned a quarter turn anticlockwise for each step of its orientation, with its
+    /// border taken off.
+    fn image(&self) -> Grid2D<bool> {
+        let mut scans = self.tile.scans.clone();
+        for _ in 0..(4 - self.orientation) % 4 {
+            scans = scans.rotated();
+        }
+        Grid2D::from_rows(scans.rows().skip(1).take(8).map(|row| row[1..9].to_vec()).collect())
+    }
+}
+
+#[derive(Debug)]
+struct Tile {
+    index: u16,
+    scans: Grid2D<bool>
+}
+
+impl Tile {
+    fn new(index: u16, scans: Grid2D<bool>) -> Self {
+        Tile { index, scans }
+    }
+
+    fn flipped(&self) -> Self {
+        Self{
+            index:self.index, scans: self.scans.flipped()
+        }
+    }
+
+    /// Whether the edge reads left to right or top to bottom: the top and right edges do, the others don't.
+    fn edge_orientation(edge: u8) -> bool {
+        edge % 4 < 2
+    }
+
+    fn key_from_vec(vals: &[bool]) -> u16 {
+        let mut key = 0;
+        for (i, v) in vals.iter().enumerate() {
+            if *v {
+                key += 2_u16.pow(i.try_into().unwrap());
+            }
+        }
+        key
+    }
+
+    fn key(&self, side: u8, shift: u8) -> u16 {
+        let shifted = (side + shift) % 4;
+        
+        let mut key_vec = self.key_vec(shifted);
+        if Self::edge_orientation(side) != Self::edge_orientation(shifted) {
+            key_vec.reverse();
+        }
+        
+        Self::key_from_vec(&key_vec)
+    }
+
+    /// The pixels along a side, counted clockwise from the top.
+    fn key_vec(&self, side: u8) -> Vec<bool> {
+        match side % 4 {
+            0 => self.scans.rows().next().unwrap().to_vec(),
+            1 => self.scans.rows().map(|x| *x.last().unwrap()).collect(),
+            2 => self.scans.rows().last().unwrap().to_vec(),
+            _ => self.scans.rows().map(|x| *x.first().unwrap()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data20.txt")
+    }
+
+    #[test]
+    fn test_small() {
+        let data_small = include_str!("../../data/data20_small.txt");
+        let jigsaw = parse_tiles(data_small).unwrap();
+        assert_eq!((20_899_048_083_289, 273), (corner_product(&jigsaw), turbulence(&jigsaw)));
+
+        // Each monster is fifteen pixels, and the rest is what's left over.
+        let chart = chart_with_monsters(&jigsaw);
+        assert_eq!((2 * 15, 273), (chart.matches('O').count(), chart.matches('#').count()));
+    }
+
+    #[test]
+    fn test_tile_numbers() {
+        // Tile numbers needn't be four digits, and the tiles can come from any editor.
+        let tile = "Tile 7:\r\n".to_string() + &"#.........\r\n".repeat(10);
+        assert_eq!(7, tiles_from_file(&input::normalise(&tile)).unwrap()[0].1.index);
+        let err = tiles_from_file("Tile 12a:\n").unwrap_err();
+        assert_eq!((1, 6), (err.line, err.col));
+
+        // A tile cut short is an error, whether another tile or the end comes first.
+        let short = "Tile 7:\n".to_string() + &"#.........\n".repeat(9);
+        let err = tiles_from_file(&(short.clone() + "Tile 8:\n")).unwrap_err();
+        assert_eq!((11, "10 rows of pixels"), (err.line, err.expected.as_str()));
+        assert_eq!(11, tiles_from_file(&short).unwrap_err().line);
+    }
+
+    #[test]
+    fn test_bad_jigsaw() {
+        let err = |data: &str| parse_tiles(data).map(|_| ()).map_err(|err| (err.line, err.expected)).unwrap_err();
+        assert_eq!((1, String::from("a tile")), err(""));
+
+        // A tile on its own has nothing to fit against, and nor does one added to a whole jigsaw.
+        let tile = "Tile 7:\n".to_string() + &"#.........\n".repeat(10);
+        assert_eq!((1, String::from("a tile that fits the jigsaw")), err(&tile));
+        let small = include_str!("../../data/data20_small.txt");
+        let lines = small.lines().count();
+        let extra = "Tile 9:\n".to_string() + &"..#.......\n".repeat(10);
+        assert_eq!((lines + 2, String::from("a tile that fits the jigsaw")), err(&format!("{}\n{}", small, extra)));
+    }
+
+    #[test]
+    fn test_all() {
+        let jigsaw = parse_tiles(data()).unwrap();
+        assert_eq!((7_492_183_537_913, 2323), (corner_product(&jigsaw), turbulence(&jigsaw)));
+    }
+}
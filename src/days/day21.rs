@@ -0,0 +1,252 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::{debug, Answer, ParseError, Rng, Solver};
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Input = Vec<FoodStuff>;
+
+    fn id(&self) -> u32 {
+        21
+    }
+
+    fn title(&self) -> &'static str {
+        "Allergen Assessment"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_foods(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        safe_uses(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        canonical_list(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        Some(generate_foods(rng, scale.unwrap_or(30)))
+    }
+}
+
+const ALLERGENS: [&str; 8] = ["dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "shellfish", "soy"];
+
+/// Makes up `count` food labels, each listing some (but not always all) of the allergens in it.
+///
+/// Allergen `i` is in the `i`th ingredient and no other. Random labels don't always pin every allergen down to one
+/// ingredient, so it tries a few times for labels that do.
+pub fn generate_foods(rng: &mut Rng, count: usize) -> String {
+    let mut ingredients = HashSet::new();
+    while ingredients.len() < 200 {
+        ingredients.insert((0..rng.between(3, 8)).map(|_| rng.letter()).collect::<String>());
+    }
+    let mut ingredients: Vec<String> = ingredients.into_iter().collect();
+    ingredients.sort_unstable();
+    rng.shuffle(&mut ingredients);
+
+    let mut foods = String::new();
+    for _ in 0..20 {
+        foods.clear();
+        for i in 0..count.max(ALLERGENS.len()) {
+            // Every allergen is listed at least once.
+            let mut listed = vec![i % ALLERGENS.len()];
+            listed.extend((0..rng.below(3)).map(|_| rng.below(ALLERGENS.len())));
+            listed.sort_unstable();
+            listed.dedup();
+
+            let mut contents: HashSet<&str> = listed.iter().map(|&a| ingredients[a].as_str()).collect();
+            if rng.chance(0.3) {
+                contents.insert(&ingredients[rng.below(ALLERGENS.len())]);
+            }
+            for _ in 0..rng.between(20, 60) {
+                contents.insert(&ingredients[ALLERGENS.len() + rng.below(200 - ALLERGENS.len())]);
+            }
+            let mut contents: Vec<&str> = contents.into_iter().collect();
+            contents.sort_unstable();
+            rng.shuffle(&mut contents);
+
+            let listed: Vec<&str> = listed.iter().map(|&a| ALLERGENS[a]).collect();
+            writeln!(foods, "{} (contains {})", contents.join(" "), listed.join(", ")).unwrap();
+        }
+
+        if let Ok(parsed) = parse_foods(&foods) {
+            if canonical_list(&parsed).split(',').count() == ALLERGENS.len() {
+                break;
+            }
+        }
+    }
+    foods
+}
+
+pub fn parse_foods(data: &str) -> Result<Vec<FoodStuff>, ParseError> {
+    let parsed = DParser::parse(Rule::file, data)
+        .map_err(|e| ParseError::from_pest(21, data, &e))?
+        .next()
+        .unwrap();
+
+    let mut foods: Vec<FoodStuff> = vec![];
+    for line in parsed.into_inner() {
+        let mut food = FoodStuff::new();
+        for part in line.into_inner() {
+            match part.as_rule() {
+                Rule::ingredient => {
+                    food.ingredients.insert(part.as_str().to_string());
+                }
+                Rule::allergen => {
+                    food.allergens.insert(part.as_str().to_string());
+                }
+                _ => panic!("Unexpected rule."),
+            }
+        }
+        foods.push(food);
+    }
+    Ok(foods)
+}
+
+/// For each allergen, the ingredients that could contain it.
+fn possible_foods(foods: &[FoodStuff]) -> HashMap<&str, Vec<&str>> {
+    // Find all the allergens
+    let mut all_allergens: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, food) in foods.iter().enumerate() {
+        for allergen in &food.allergens {
+            all_allergens
+                .entry(allergen)
+                .or_default()
+                .push(i);
+        }
+    }
+
+    let mut possible_foods = HashMap::new();
+
+    // For each allergen, we find the intersection of ingredients that can contain it.
+    for (allergen, indexed_foods) in all_allergens {
+        let mut foods_intersect = HashSet::new();
+        for food in indexed_foods {
+            let ingredients = foods.get(food).unwrap().ingredients.iter().map(String::as_str).collect();
+            if foods_intersect.is_empty() {
+                foods_intersect = ingredients;
+            } else {
+                foods_intersect = foods_intersect
+                    .intersection(&ingredients)
+                    .copied()
+                    .collect();
+            }
+        }
+        possible_foods.insert(allergen, foods_intersect.into_iter().collect());
+    }
+    possible_foods
+}
+
+/// How many times ingredients that can't contain an allergen appear.
+pub fn safe_uses(foods: &[FoodStuff]) -> u64 {
+    let dangerous_foods: HashSet<&str> = possible_foods(foods).into_values().flatten().collect();
+    let mut safe_uses = 0;
+    for food in foods {
+        for ingredient in &food.ingredients {
+            if !dangerous_foods.contains(ingredient.as_str()) {
+                safe_uses += 1;
+            }
+        }
+    }
+    safe_uses
+}
+
+/// The dangerous ingredients, comma separated, in allergen order.
+pub fn canonical_list(foods: &[FoodStuff]) -> String {
+    let possible_foods = possible_foods(foods);
+
+    // Work out the dangerous ingredients.
+    let mut dangerous: HashMap<&str, &str> = HashMap::new();
+    let mut fixed = HashSet::new();
+
+    loop {
+        let mut count_fixed = 0;
+
+        for (allergen, foods) in &possible_foods {
+            if dangerous.contains_key(allergen) {
+                continue;
+            }
+
+            let mut options = vec![];
+            for f in foods {
+                if !fixed.contains(f) {
+                    options.push(*f);
+                }
+            }
+
+            if options.len() == 1 {
+                let bad_ingredient = options[0];
+                fixed.insert(bad_ingredient);
+                dangerous.insert(allergen, bad_ingredient);
+                count_fixed += 1;
+            }
+        }
+        if count_fixed == 0 {
+            break;
+        }
+    }
+
+    let mut sorted_danger: Vec<(&str, &str)> = dangerous.into_iter().collect();
+    sorted_danger.sort_unstable();
+    for (allergen, ingredient) in &sorted_danger {
+        debug!("{} in {}", allergen, ingredient);
+    }
+    let canonical_ingredients: Vec<_> = sorted_danger.iter().map(|(_, ingredient)| *ingredient).collect();
+    canonical_ingredients.join(",")
+}
+
+/// One food label: what's in it, and some (not always all) of the allergens in that.
+#[derive(Debug)]
+pub struct FoodStuff {
+    pub ingredients: HashSet<String>,
+    pub allergens: HashSet<String>,
+}
+
+impl FoodStuff {
+    fn new() -> Self {
+        FoodStuff {
+            ingredients: HashSet::new(),
+            allergens: HashSet::new(),
+        }
+    }
+}
+
+#[derive(Parser)]
+#[grammar = "parsers/day21.pest"]
+pub struct DParser {}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data21.txt")
+    }
+
+    fn report(data: &str) -> (u64, String) {
+        let foods = parse_foods(data).unwrap();
+        (safe_uses(&foods), canonical_list(&foods))
+    }
+
+    #[test]
+    fn test_small() {
+        let data_small = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+        assert_eq!((5, String::from("mxmxvkd,sqjhc,fvjkl")), report(data_small));
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!((1679, String::from("lmxt,rggkbpj,mxf,gpxmf,nmtzlj,dlkxsxg,fvqg,dxzq")), report(data()));
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::{HashSet, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+
+use crate::{cancel, crab, input, Answer, ParseError, Rng, Solver};
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input = Decks;
+
+    fn id(&self) -> u32 {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Crab Combat"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        let mut decks = [VecDeque::new(), VecDeque::new()];
+        let mut player = 0;
+        for (n, line) in input::lines(data) {
+            if player < 2 && line == format!("Player {}:", player + 1) {
+                if player > 0 && decks[player - 1].is_empty() {
+                    return Err(ParseError::at_line(22, data, n, 1, "a card"));
+                }
+                player += 1;
+            } else if player == 0 {
+                return Err(ParseError::at_line(22, data, n, 1, "Player 1:"));
+            } else {
+                let card = line.parse().map_err(|_| {
+                    let expected = if player == 1 { "a card or Player 2:" } else { "a card" };
+                    ParseError::at_line(22, data, n, 1, expected)
+                })?;
+                decks[player - 1].push_back(card);
+            }
+        }
+        let [one, two] = decks;
+        match player {
+            0 => Err(ParseError::at(22, data, data.len(), "Player 1:")),
+            1 if one.is_empty() => Err(ParseError::at(22, data, data.len(), "a card")),
+            1 => Err(ParseError::at(22, data, data.len(), "Player 2:")),
+            _ if two.is_empty() => Err(ParseError::at(22, data, data.len(), "a card")),
+            _ => Ok((one, two)),
+        }
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        winner_simple(input).score.into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        run_rgame(input).score.into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // `scale` cards in all, dealt out between the two players.
+        let count = scale.unwrap_or(50).max(2);
+        let mut cards: Vec<usize> = (1..=count).collect();
+        rng.shuffle(&mut cards);
+        let deck = |cards: &[usize]| cards.iter().map(|c| c.to_string() + "\n").collect::<String>();
+        Some(format!(
+            "Player 1:\n{}\nPlayer 2:\n{}",
+            deck(&cards[..count / 2]),
+            deck(&cards[count / 2..])
+        ))
+    }
+}
+
+/// The two players' decks, top card first: mine, then the crab's.
+pub type Decks = (VecDeque<u64>, VecDeque<u64>);
+
+/// Who won a game of Combat, and the score of their deck.
+#[derive(Debug, PartialEq)]
+pub struct GameResult {
+    /// "ME" or the crab.
+    pub winner: String,
+    pub score: u64,
+}
+
+/// Plays a plain game of Combat.
+pub fn winner_simple(game: &Decks) -> GameResult {
+    let (winner, winning_deck) = play_game(game);
+    let winner_name = if winner == 2 { crab() } else { String::from("ME") };
+    GameResult {
+        winner: winner_name,
+        score: score_deck(&winning_deck),
+    }
+}
+
+fn score_deck(deck: &VecDeque<u64>) -> u64 {
+    let max = deck.len();
+    let mut total_score = 0;
+    for (i, card) in deck.iter().enumerate() {
+        let multiplier = u64::try_from(max - i).unwrap();
+        let score = multiplier * card;
+        total_score += score;
+    }
+
+    total_score
+}
+
+fn play_game(game: &Decks) -> (u8, VecDeque<u64>) {
+    let (mut deck_one, mut deck_two) = game.clone();
+
+    // Without the recursive game's loop rule, some decks never finish.
+    while !deck_one.is_empty() && !deck_two.is_empty() {
+        cancel::check();
+        let (card1, card2) = (deck_one.pop_front().unwrap(), deck_two.pop_front().unwrap());
+        if card1 > card2 {
+            deck_one.push_back(card1);
+            deck_one.push_back(card2);
+        } else {
+            deck_two.push_back(card2);
+            deck_two.push_back(card1);
+        }
+    }
+
+    if deck_one.is_empty() {
+        (2, deck_two)
+    } else {
+        (1, deck_one)
+    }
+}
+
+/// Plays a game of Recursive Combat.
+pub fn run_rgame(game: &Decks) -> GameResult {
+    let (deck_one, deck_two) = game.clone();
+    let (winner, score) = play_rgame(deck_one, deck_two);
+
+    let winner_name = if winner == 2 { crab() } else { String::from("ME") };
+    GameResult {
+        winner: winner_name,
+        score,
+    }
+}
+
+fn deck_key(deck_one: &VecDeque<u64>, deck_two: &VecDeque<u64>) -> u64 {
+    let mut s = DefaultHasher::new();
+    (deck_one, deck_two).hash(&mut s);
+    s.finish()
+}
+
+fn copy_deck(from: &VecDeque<u64>, count: u64) -> VecDeque<u64> {
+    let card_count: usize = usize::try_from(count).unwrap();
+    let mut new_deck = VecDeque::new();
+    for card in 0..card_count {
+        new_deck.push_back(*from.get(card).unwrap());
+    }
+
+    new_deck
+}
+
+fn play_rgame(mut deck_one: VecDeque<u64>, mut deck_two: VecDeque<u64>) -> (u8, u64) {
+    let mut games_seen = HashSet::new();
+    let mut winner = 0;
+    while !deck_one.is_empty() && !deck_two.is_empty() {
+        // Have we seen this combination before?
+        let deck_key = deck_key(&deck_one, &deck_two);
+        if games_seen.contains(&deck_key) {
+            winner = 1;
+            break;
+        }
+
+        games_seen.insert(deck_key);
+        let (card1, card2) = (deck_one.pop_front().unwrap(), deck_two.pop_front().unwrap());
+        if usize::try_from(card1).unwrap() <= deck_one.len()
+            && usize::try_from(card2).unwrap() <= deck_two.len()
+        {
+            // We *can recurse!
+            let sub_deck1 = copy_deck(&deck_one, card1);
+            let sub_deck2 = copy_deck(&deck_two, card2);
+            let (sub_winner, _) = play_rgame(sub_deck1, sub_deck2);
+            winner = sub_winner;
+        } else {
+            // We can't recurse. Highest wins.
+            winner = if card1 > card2 { 1 } else { 2 };
+        }
+
+        // Winner collects the cards.
+        if winner == 1 {
+            deck_one.push_back(card1);
+            deck_one.push_back(card2);
+        } else {
+            deck_two.push_back(card2);
+            deck_two.push_back(card1);
+        }
+    }
+
+    // If either card deck is empty, they are the winner, work out the score.
+    // Otherwise, we must have finshed due to a repeating position.
+    if deck_two.is_empty() {
+        (1, score_deck(&deck_one))
+    } else if deck_one.is_empty() {
+        (2, score_deck(&deck_two))
+    } else if winner == 1 {
+        (1, score_deck(&deck_one))
+    } else if winner == 2 {
+        (2, score_deck(&deck_two))
+    } else {
+        panic!("Inavlid game result.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data22.txt")
+    }
+
+    fn crab_wins(score: u64) -> GameResult {
+        GameResult { winner: crab(), score }
+    }
+
+    #[test]
+    fn test_small() {
+        let small_game = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+        let small_game = Day22.parse(small_game).unwrap();
+        assert_eq!(crab_wins(306), winner_simple(&small_game));
+        assert_eq!(crab_wins(291), run_rgame(&small_game));
+    }
+
+    #[test]
+    fn test_bad_decks() {
+        let err = |data: &str| Day22.parse(data).map_err(|err| (err.line, err.expected)).unwrap_err();
+        assert_eq!((1, String::from("Player 1:")), err("9\n2\n"));
+        assert_eq!((3, String::from("a card or Player 2:")), err("Player 1:\n9\nPlayer two:\n5\n"));
+        assert_eq!((4, String::from("Player 2:")), err("Player 1:\n9\n2\n"));
+        assert_eq!((2, String::from("a card")), err("Player 1:\nPlayer 2:\n5\n"));
+        assert_eq!((5, String::from("a card")), err("Player 1:\n9\n\nPlayer 2:\n"));
+        assert_eq!((5, String::from("a card")), err("Player 1:\n9\nPlayer 2:\n5\nx\n"));
+    }
+
+    #[test]
+    fn test_all() {
+        let game = Day22.parse(data()).unwrap();
+        assert_eq!(crab_wins(32783), winner_simple(&game));
+        assert_eq!(crab_wins(33455), run_rgame(&game));
+    }
+}
@@ -0,0 +1,282 @@
+use std::convert::TryFrom;
+
+use crate::checkpoint::Checkpoint;
+use crate::params::Param;
+use crate::progress::Progress;
+use crate::{cancel, debug, input_hash, params, Answer, ParseError, Rng, Solver};
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Input = String;
+
+    fn id(&self) -> u32 {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "Crab Cups"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "part1_moves", valid: params::reads_as::<usize> },
+            Param { name: "cups", valid: params::reads_as::<usize> },
+            Param { name: "part2_moves", valid: params::reads_as::<usize> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        let labels = data.trim();
+        let start = data.find(labels).unwrap_or(0);
+        // The cups must be labelled 1 to however many there are, each once, and there must be more than the three
+        // picked up each move.
+        let count = labels.chars().count();
+        if count < 4 {
+            return Err(ParseError::at(23, data, start + labels.len(), "at least four cup labels"));
+        }
+        let mut seen = [false; 10];
+        for (i, c) in labels.char_indices() {
+            match c.to_digit(10) {
+                Some(n) if n == 0 || n as usize > count => {
+                    let expected = format!("a cup label from 1 to {}", count);
+                    return Err(ParseError::at(23, data, start + i, expected));
+                }
+                Some(n) if seen[n as usize] => {
+                    return Err(ParseError::at(23, data, start + i, "a cup label not already used"));
+                }
+                Some(n) => seen[n as usize] = true,
+                None => return Err(ParseError::at(23, data, start + i, "a cup label (1 to 9)")),
+            }
+        }
+        Ok(labels.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        play_game(input, params::get(23, "part1_moves", 100)).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let cups = params::get(23, "cups", 1_000_000).max(input.len());
+        play_big_game(input, cups, params::get(23, "part2_moves", 10_000_000)).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, _scale: Option<usize>) -> Option<String> {
+        // There are always nine cups, whatever the scale.
+        let mut labels: Vec<u32> = (1..=9).collect();
+        rng.shuffle(&mut labels);
+        Some(labels.iter().map(ToString::to_string).collect::<String>() + "\n")
+    }
+
+    fn reference(&self, input: &Self::Input, part: u32) -> Option<Answer> {
+        // A million cups are far too many to shuffle about in a Vec.
+        (part == 1).then(|| play_game_naive(input, params::get(23, "part1_moves", 100)).into())
+    }
+}
+
+/// Plays with the labelled cups followed by the rest up to `cups`, giving the product of the two cups after cup 1.
+pub fn play_big_game(init: &str, cups: usize, rounds: usize) -> u64 {
+    let key = input_hash(&format!("{} {} {}", init, cups, rounds));
+    play_big_game_with(init, cups, rounds, &mut Checkpoint::new(23, key))
+}
+
+/// Plays the big game, carrying on from the checkpoint's snapshot if it has one and saving more along the way.
+fn play_big_game_with(init: &str, cups: usize, rounds: usize, checkpoint: &mut Checkpoint) -> u64 {
+    let (start, mut game, mut focus) = match checkpoint.resume() {
+        Some(snapshot) if snapshot.state.len() == cups + 1 => {
+            debug!("Carrying on from move {}", snapshot.done);
+            let focus = usize::try_from(snapshot.extra).unwrap();
+            (usize::try_from(snapshot.done).unwrap(), snapshot.state.into_boxed_slice(), focus)
+        }
+        _ => {
+            let (game, focus) = init_smart_game(init, cups);
+            (0, game, focus)
+        }
+    };
+    let mut progress = Progress::new("Day 23 moves", Some(rounds as u64));
+    for round in start..rounds {
+        if round % 65536 == 0 {
+            cancel::check();
+            progress.update(round as u64);
+            if checkpoint.due() {
+                checkpoint.save(round as u64, focus as u64, &game);
+            }
+        }
+        focus = smart_round(cups, &mut game, focus);
+    }
+
+    // Find left of one.
+    let after_one = *game.get(1).unwrap();
+    let next_after_one = *game.get(after_one as usize).unwrap();
+
+    let grand_product = u64::from(after_one) * u64::from(next_after_one);
+    debug!("The values after 1 are: {} x {} ==> {}", after_one, next_after_one, grand_product);
+    grand_product
+}
+/// Plays with just the labelled cups, giving the labels after cup 1.
+pub fn play_game(init: &str, rounds: usize) -> String {
+    let cups = init.len();
+    let (mut game, mut focus) = init_smart_game(init, cups);
+    for round in 0..rounds {
+        if round % 65536 == 0 {
+            cancel::check();
+        }
+        focus = smart_round(cups, &mut game, focus);
+    }
+    from_one(cups, &game)
+} 
+
+fn from_one(count: usize, cups: &[u32]) -> String {
+    let mut code = String::new();
+
+    let mut at_cup = 1;
+    // Note we start at one, because we skip the '1'
+    for _ in 1..count {
+        let move_to = cups.get(at_cup).unwrap();
+        code.push_str(format!("{}", *move_to).as_str());
+        at_cup = *move_to as usize;
+    }
+    code
+
+}
+
+fn smart_round(max: usize, cups: &mut [u32], focus: usize) -> usize {
+    // Pop three after the focus.
+    let first_popped = *cups.get(focus).unwrap() as usize;
+    let middle_popped = *cups.get(first_popped).unwrap() as usize;
+    let last_popped = *cups.get(middle_popped).unwrap() as usize;
+
+    // The focus is reconnected to the following entry.
+    cups[focus] = cups[last_popped];
+
+    let mut insert_at = focus - 1;
+    if insert_at == 0 {
+        insert_at = max;
+    }
+    
+    while
+        (insert_at == first_popped)
+        || (insert_at == middle_popped)
+        || (insert_at == last_popped) {
+            insert_at -= 1;
+            if insert_at == 0 {
+                insert_at = max;
+            }
+        }
+
+    // Stitch in the popped values.
+    let end_of_stitch = *cups.get(insert_at).unwrap();
+    cups[insert_at] = u32::try_from(first_popped).unwrap();
+    cups[last_popped] = end_of_stitch;
+
+    // Return the new focus.
+    cups[focus] as usize
+}
+
+fn init_smart_game(init: &str, max: usize) -> (Box<[u32]> , usize) {
+    let mut game = vec![0_u32; max + 1].into_boxed_slice();
+    let vals =  init.chars().filter_map(|x| x.to_string().parse::<u32>().ok());
+    let mut previous = None;
+    let mut first = 0;
+    for val in vals {
+        if let Some(p) = previous  {
+            game[p as usize] = val;
+        } else {
+            first = val;
+        }
+        previous = Some(val);
+    }
+
+    // Now, need to add the remainder.
+    for i in (init.len() + 1)..=max {
+        if let Some(p) = previous  {
+            game[p as usize] = u32::try_from(i).unwrap();
+        }
+        previous = Some(u32::try_from(i).unwrap());
+    }
+
+    // Finally, the last value loops.
+    if let Some(p) = previous {
+        game[p as usize] = first;
+    }
+    (game, first as usize)
+}
+
+/// Plays with just the labelled cups, moving them about in a `Vec` as the puzzle tells it. The reference for
+/// `play_game`, which keeps each cup's clockwise neighbour instead.
+fn play_game_naive(init: &str, rounds: usize) -> String {
+    let mut cups: Vec<u32> = init.chars().filter_map(|c| c.to_digit(10)).collect();
+    let max = cups.iter().copied().max().unwrap_or(0);
+    for _ in 0..rounds {
+        // The current cup is always kept at the front.
+        let current = cups[0];
+        let picked: Vec<u32> = cups.drain(1..4).collect();
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 { max } else { destination - 1 };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        let at = cups.iter().position(|&c| c == destination).unwrap() + 1;
+        cups.splice(at..at, picked);
+        cups.rotate_left(1);
+    }
+
+    let one = cups.iter().position(|&c| c == 1).unwrap();
+    cups.rotate_left(one);
+    cups[1..].iter().map(ToString::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use crate::checkpoint;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data23.txt")
+    }
+
+    #[test]
+    fn test_small_game() {
+        
+        let demo_data = "389125467";
+        assert_eq!("92658374", play_game(demo_data, 10));
+        assert_eq!("67384529", play_game(demo_data, 100));
+    }
+
+    #[test]
+    fn test_big_game() {
+        let demo_data = "389125467";
+        assert_eq!(149_245_887_792, play_big_game(demo_data, 1_000_000, 10_000_000));
+    }
+
+    #[test]
+    fn test_resume() {
+        let demo_data = "389125467";
+        let product = play_big_game(demo_data, 1000, 100_000);
+        let (mut checkpoint, saved) = checkpoint::tests::saving(23, 1, "day23", None);
+        assert_eq!(product, play_big_game_with(demo_data, 1000, 100_000, &mut checkpoint));
+        let snapshot = saved();
+        assert_eq!(65536, snapshot.done);
+        let (mut checkpoint, _) = checkpoint::tests::saving(23, 1, "day23-resumed", Some(snapshot));
+        assert_eq!(product, play_big_game_with(demo_data, 1000, 100_000, &mut checkpoint));
+    }
+
+    #[test]
+    fn test_bad_labels() {
+        let err = |data: &str| Day23.parse(data).map_err(|err| (err.col, err.expected)).unwrap_err();
+        assert_eq!((3, String::from("a cup label from 1 to 4")), err("1365\n"));
+        assert_eq!((2, String::from("a cup label not already used")), err("1123\n"));
+        assert_eq!((3, String::from("at least four cup labels")), err("21\n"));
+        assert_eq!((3, String::from("a cup label (1 to 9)")), err("12x4\n"));
+        assert!(Day23.parse("4132\n").is_ok());
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!("89372645", play_game(data().trim(), 100));
+        assert_eq!(21_273_394_210, play_big_game(data().trim(), 1_000_000, 10_000_000));
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use crate::life::{self, Hex, Sparse};
+use crate::params::Param;
+use crate::{input, params, Answer, ParseError, Rng, Solver};
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Input = HashSet<(i64, i64)>;
+
+    fn id(&self) -> u32 {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Lobby Layout"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "days", valid: params::reads_as::<usize> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_tiles(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        input.len().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        after_days(input, params::get(24, "days", 100)).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut tiles = String::new();
+        for _ in 0..scale.unwrap_or(420) {
+            for _ in 0..rng.between(12, 25) {
+                let step = rng.pick(&["e", "se", "sw", "w", "nw", "ne"]);
+                tiles.push_str(step);
+            }
+            tiles.push('\n');
+        }
+        Some(tiles)
+    }
+}
+
+/// The tiles that are black side up once the instructions have been followed, at axial `(x, y)` coordinates.
+pub fn parse_tiles(data: &str) -> Result<HashSet<(i64, i64)>, ParseError> {
+    // Each line is non-delimited e/se/ne/w/nw/ne
+    // We pick our coordinate system s.t. ne/sw is on diagonal, nw, se on constant.
+    const EXPECTED: &str = "e, se, sw, w, nw or ne";
+    let mut black_tiles = HashSet::new();
+
+    for (n, line) in input::lines(data) {
+        let mut x = 0_i64;
+        let mut y = 0_i64;
+        let mut previous = None;
+
+        for (i, c) in line.chars().enumerate() {
+            let col = i + 1;
+            match c {
+                's' | 'n' => {
+                    if previous.is_some() {
+                        return Err(ParseError::at_line(24, data, n, col, EXPECTED));
+                    }
+                    previous = Some(c); // We stash the n/s to resolve later.
+                },
+                'e' => {
+                    match previous {
+                        None => {
+                            x += 1;
+                        },
+                        Some('n') => {
+                            y += 1; // ne is on the daigonal.
+                            x += 1;
+                        },
+                        _ => {
+                            y -= 1; // se is on the vertical.
+                        },
+                    }
+                    previous = None;
+                },
+                'w' => {
+                    match previous {
+                        None => {
+                            x -= 1;
+                        },
+                        Some('n') => {
+                            y += 1; // nw is on the vertical.
+                        },
+                        _ => {
+                            y -= 1;
+                            x -= 1; // sw is on the diagonal.
+                        },
+                    }
+                    previous = None;
+                },
+                _ => return Err(ParseError::at_line(24, data, n, col, EXPECTED)),
+            }
+        }
+
+        if previous.is_some() {
+            // A north or south with no east or west to finish it.
+            return Err(ParseError::at_line(24, data, n, line.chars().count() + 1, EXPECTED));
+        }
+
+        if black_tiles.contains(&(x, y)) {
+            black_tiles.remove(&(x, y));
+        } else {
+            black_tiles.insert((x, y));
+        }
+    }
+
+    Ok(black_tiles)
+}
+
+/// How many tiles are black side up after the floor has changed every day for `days` days: a black tile with none or
+/// more than two black neighbours turns white, and a white one with exactly two turns black.
+pub fn after_days(tiles: &HashSet<(i64, i64)>, days: usize) -> usize {
+    let mut floor = Sparse::new(Hex, "B2/S12".parse().unwrap(), tiles.clone());
+    life::populations(&mut floor, days, |_, _| {})[days]
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data24.txt")
+    }
+
+    #[test]
+    fn test_small() {
+        let data_small = include_str!("../../data/data24_small.txt");
+        let tiles = parse_tiles(data_small).unwrap();
+        assert_eq!((10, 2208), (tiles.len(), after_days(&tiles, 100)));
+    }
+
+    #[test]
+    fn test_bad_directions() {
+        let err = parse_tiles("esew\nnwx\n").unwrap_err();
+        assert_eq!((2, 3, "e, se, sw, w, nw or ne"), (err.line, err.col, err.expected.as_str()));
+        assert_eq!((1, 2), parse_tiles("nse\n").map_err(|err| (err.line, err.col)).unwrap_err());
+        assert_eq!((1, 4), parse_tiles("sen\n").map_err(|err| (err.line, err.col)).unwrap_err());
+    }
+
+    #[test]
+    fn test_all() {
+        let tiles = parse_tiles(data()).unwrap();
+        assert_eq!((377, 4231), (tiles.len(), after_days(&tiles, 100)));
+    }
+}
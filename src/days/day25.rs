@@ -0,0 +1,107 @@
+use crate::{cancel, input, Answer, ParseError, Rng, Solver};
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    type Input = (u64, u64);
+
+    fn id(&self) -> u32 {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "Combo Breaker"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        let mut keys = vec![];
+        for (n, line) in input::lines(data) {
+            if keys.len() == 2 {
+                return Err(ParseError::at_line(25, data, n, 1, "only two public keys"));
+            }
+            match line.trim().parse() {
+                Ok(key) => keys.push(key),
+                Err(_) => return Err(ParseError::at_line(25, data, n, 1, "a public key (a number)")),
+            }
+        }
+        match keys[..] {
+            [card, door] => Ok((card, door)),
+            _ => Err(ParseError::at(25, data, data.len(), "a card and a door public key")),
+        }
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        crack(input.0, input.1).into()
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        // There is no second puzzle on the last day.
+        Answer::from("\u{2B50}")
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // Loop sizes up to `scale`, which is how long cracking them takes.
+        let most = scale.unwrap_or(10_000_000).clamp(1, 20_201_226);
+        let card = transform(7, 1 + rng.below(most));
+        let door = transform(7, 1 + rng.below(most));
+        Some(format!("{}\n{}\n", card, door))
+    }
+}
+
+/// The encryption key the card and door agree on, from their public keys.
+pub fn crack(card: u64, door: u64) -> u64 {
+    // The card/door keys are found by transforming 7.
+    let card_lp = crack_transform(7, card);
+    assert_eq!(card, transform(7, card_lp));
+
+    let door_lp = crack_transform(7, door);
+    assert_eq!(door, transform(7, door_lp));
+
+    // The encryption key is found by either transforming the others key.
+    transform(door, card_lp)
+}
+
+/// The loop size that transforms `subject` into `target`.
+pub fn crack_transform(subject: u64, target: u64) -> usize {
+    let mut value = subject;
+    let mut lp = 1;
+    loop {
+        if value == target {
+            break lp
+        }
+        lp += 1;
+        value = (value * subject) % 20_201_227_u64;
+        if lp % 65536 == 0 {
+            cancel::check();
+        }
+    }
+}
+
+pub fn transform(subject: u64, lp: usize) -> u64 {
+    let mut value: u64 = 1;
+    for _ in 0..lp {
+        value = (value * subject) % 20_201_227_u64;
+    }
+    value
+} 
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data25.txt")
+    }
+
+    #[test]
+    fn test_small() {
+        assert_eq!(14_897_079, crack(5_764_801, 17_807_724));
+    }
+
+    #[test]
+    fn test_all() {
+        let (card, door) = Day25.parse(data()).unwrap();
+        assert_eq!(181_800, crack(card, door));
+    }
+}
@@ -0,0 +1,104 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::params::Param;
+use crate::{debug, params, Answer, Grid2D, ParseError, Rng, Solver};
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Grid2D<bool>;
+
+    fn id(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Toboggan Trajectory"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "slope", valid: params::reads_as::<Slope> },
+            Param { name: "slopes", valid: params::list_of::<Slope> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        Grid2D::parse(3, data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let slope = params::get(3, "slope", Slope { right: 3, down: 1 });
+        trees_hit(input, slope).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let trajs = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].map(|(right, down)| Slope { right, down });
+        params::get_list(3, "slopes", &trajs)
+            .into_iter()
+            .map(|slope| trees_hit(input, slope))
+            .product::<usize>()
+            .into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut map = String::new();
+        for _ in 0..scale.unwrap_or(323) {
+            map.extend((0..31).map(|_| if rng.chance(0.2) { '#' } else { '.' }));
+            map.push('\n');
+        }
+        Some(map)
+    }
+}
+
+/// How far across and down the toboggan goes each step, written as e.g. `3/1` in the parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slope {
+    pub right: usize,
+    pub down: usize,
+}
+
+impl FromStr for Slope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (right, down) = s.split_once('/').ok_or(())?;
+        match (right.parse(), down.parse()) {
+            (Ok(right), Ok(down)) if down > 0 => Ok(Slope { right, down }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How many trees (`#`) the toboggan hits going down the forest at `slope`, which repeats to the right.
+pub fn trees_hit(forest: &Grid2D<bool>, slope: Slope) -> usize {
+    let trees = (0..forest.height())
+        .step_by(slope.down.max(1))
+        .filter(|&row| {
+            let col = slope.right * row / slope.down;
+            *forest.wrapping(isize::try_from(row).unwrap(), isize::try_from(col).unwrap())
+        })
+        .count();
+
+    debug!("Going {}, {}, hit {} trees \u{1F61E}", slope.right, slope.down, trees);
+
+    trees
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_test() {
+        let forest = Grid2D::parse(3, include_str!("../../data/data3.txt")).unwrap();
+        let hit = |right, down| trees_hit(&forest, Slope { right, down });
+        assert_eq!(68, hit(1, 1));
+        assert_eq!(203, hit(3, 1));
+        assert_eq!(78, hit(5, 1));
+        assert_eq!(77, hit(7, 1));
+        assert_eq!(40, hit(1, 2));
+    }
+}
@@ -0,0 +1,180 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use crate::{Answer, ParseError, Rng, Solver};
+
+#[derive(Parser)]
+#[grammar = "parsers/day4.pest"]
+pub struct DParser {}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = Vec<Passport>;
+
+    fn id(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Passport Processing"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_file(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        complete(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        valid(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut passports = vec![];
+        for _ in 0..scale.unwrap_or(280) {
+            let mut fields = vec![];
+            for field in ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+                if rng.chance(if field == "cid" { 0.5 } else { 0.9 }) {
+                    let valid = rng.chance(0.85);
+                    fields.push(format!("{}:{}", field, field_value(rng, field, valid)));
+                }
+            }
+            if fields.is_empty() {
+                fields.push(format!("cid:{}", rng.between(1, 999)));
+            }
+            rng.shuffle(&mut fields);
+
+            let mut passport = String::new();
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+                }
+                passport.push_str(field);
+            }
+            passports.push(passport);
+        }
+        Some(passports.join("\n\n") + "\n")
+    }
+}
+
+/// What a passport has in it, as far as the checks are concerned.
+#[derive(Debug, PartialEq)]
+pub struct Passport {
+    /// How many of the seven required fields are there (`cid` is optional).
+    pub required: usize,
+    /// How many of those hold a valid value.
+    pub valid: usize,
+}
+
+impl Passport {
+    /// Has all the required fields.
+    pub fn is_complete(&self) -> bool {
+        self.required == 7
+    }
+
+    /// Has all the required fields, and every one of them holds a valid value.
+    pub fn is_valid(&self) -> bool {
+        self.valid == 7
+    }
+}
+
+pub fn complete(passports: &[Passport]) -> usize {
+    passports.iter().filter(|p| p.is_complete()).count()
+}
+
+pub fn valid(passports: &[Passport]) -> usize {
+    passports.iter().filter(|p| p.is_valid()).count()
+}
+
+fn is_between(val: &str, min: i32, max: i32) -> usize {
+    let parsed = val.parse::<i32>();
+    if let Ok(v) = parsed {
+        usize::from(v >= min && v <= max)
+    } else {
+        0
+    }
+}
+
+/// A made-up value for a passport field, which passes its check if `valid`.
+fn field_value(rng: &mut Rng, field: &str, valid: bool) -> String {
+    const HEX: &[u8] = b"0123456789abcdef";
+    let hex = |rng: &mut Rng| -> String { (0..6).map(|_| char::from(*rng.pick(HEX))).collect() };
+    match (field, valid) {
+        ("byr", true) => rng.between(1920, 2002).to_string(),
+        ("iyr", true) => rng.between(2010, 2020).to_string(),
+        ("eyr", true) => rng.between(2020, 2030).to_string(),
+        ("byr" | "iyr" | "eyr", false) => rng.between(1900, 2040).to_string(),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.between(150, 193)),
+        ("hgt", true) => format!("{}in", rng.between(59, 76)),
+        ("hgt", false) => match rng.below(3) {
+            0 => format!("{}cm", rng.between(100, 149)),
+            1 => format!("{}in", rng.between(77, 99)),
+            _ => rng.between(50, 200).to_string(),
+        },
+        ("hcl", true) => format!("#{}", hex(rng)),
+        ("hcl", false) => hex(rng),
+        ("ecl", true) => (*rng.pick(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])).to_string(),
+        ("ecl", false) => (*rng.pick(&["xry", "zzz", "gmt", "#1f58f9"])).to_string(),
+        ("pid", true) => format!("{:09}", rng.between(0, 999_999_999)),
+        ("pid", false) => format!("{:010}", rng.between(0, 9_999_999_999)),
+        _ => rng.between(1, 999).to_string(),
+    }
+}
+
+pub fn parse_file(unparsed_file: &str) -> Result<Vec<Passport>, ParseError> {
+    let file = DParser::parse(Rule::file, unparsed_file)
+        .map_err(|e| ParseError::from_pest(4, unparsed_file, &e))?
+        .next()
+        .unwrap();
+
+    let mut passports = vec![];
+    for line in file.into_inner() {
+        let mut fcount = 0;
+        let mut fcount2 = 0;
+        for f in line.into_inner() {
+            if f.as_rule() != Rule::cid {
+                fcount += 1;
+                let mut parts = f.into_inner();
+                let try_part = parts.next();
+                if let Some(part) = try_part {
+                    if parts.next().is_none() {
+                        fcount2 += match part.as_rule() {
+                            Rule::vbirth => is_between(part.as_str(), 1920, 2002),
+                            Rule::vissue => is_between(part.as_str(), 2010, 2020),
+                            Rule::vexpire => is_between(part.as_str(), 2020, 2030),
+                            Rule::cmheight => is_between(part.as_str(), 150, 193),
+                            Rule::inheight => is_between(part.as_str(), 59, 76),
+                            Rule::vhair | Rule::vecl | Rule::vpid => 1,
+                            _ => 0,
+                        }
+                    }
+                }
+            }
+        }
+
+        // Records with none of the required fields don't count as passports.
+        if fcount > 0 {
+            passports.push(Passport {
+                required: fcount,
+                valid: fcount2,
+            });
+        }
+    }
+    Ok(passports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_test() {
+        let cbytes = include_bytes!("../../data/data4.txt");
+        let contents = String::from_utf8_lossy(cbytes);
+        let passports = parse_file(&contents).unwrap();
+        assert_eq!((296, 239, 188), (passports.len(), complete(&passports), valid(&passports)));
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+
+use crate::{input, Answer, ParseError, Rng, Solver};
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Vec<Seat>;
+
+    fn id(&self) -> u32 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Binary Boarding"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        let mut seats = vec![];
+        for (n, pass) in input::lines(data) {
+            // Seven letters for the row, then three for the column.
+            for (i, c) in pass.chars().enumerate() {
+                let (expected, ok) = match i {
+                    0..=6 => ("F or B", c == 'F' || c == 'B'),
+                    7..=9 => ("L or R", c == 'L' || c == 'R'),
+                    _ => ("a boarding pass of 10 letters", false),
+                };
+                if !ok {
+                    return Err(ParseError::at_line(5, data, n, i + 1, expected));
+                }
+            }
+            if pass.len() < 10 {
+                return Err(ParseError::at_line(5, data, n, pass.len() + 1, "a boarding pass of 10 letters"));
+            }
+            seats.push(parse_seat(pass));
+        }
+        Ok(seats)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        find_max_seats(input).id.into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        find_missing_seat(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        // A full run of seats with one missing from the middle. There are only 1024 seats on the plane.
+        let count = scale.unwrap_or(800).clamp(3, 1000) as u64;
+        let first = rng.between(8, 1016 - count);
+        let missing = rng.between(first + 1, first + count - 1);
+        let mut ids: Vec<u64> = (first..=first + count).filter(|&id| id != missing).collect();
+        rng.shuffle(&mut ids);
+
+        let mut passes = String::new();
+        for id in ids {
+            // Seven row bits then three column bits, most significant first.
+            passes.extend((0..10).rev().map(|bit| match (bit, (id >> bit) & 1) {
+                (3..=9, 0) => 'F',
+                (3..=9, _) => 'B',
+                (_, 0) => 'L',
+                _ => 'R',
+            }));
+            passes.push('\n');
+        }
+        Some(passes)
+    }
+}
+
+/// A boarding pass, decoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Seat {
+    pub row: i64,
+    pub col: i64,
+    pub id: i64,
+}
+
+/// The one empty seat whose neighbours are both taken.
+pub fn find_missing_seat(seats: &[Seat]) -> i64 {
+    let mut seated: HashSet<i64> = HashSet::new();
+    let (mut min, mut max) = (0, 0);
+    for seat in seats {
+        let i = seat.id;
+        if min == 0 || i < min {
+            min = i;
+        }
+
+        if i > max {
+            max = i;
+        }
+
+        seated.insert(i);
+    }
+
+    for check in min..max {
+        if !seated.contains(&check) && seated.contains(&(check - 1)) && seated.contains(&(check +1)) {
+            return check;
+        }
+    }
+
+    0
+}
+
+/// The seat with the highest id.
+pub fn find_max_seats(seats: &[Seat]) -> Seat {
+    let mut best = Seat::default();
+    for &s in seats {
+        if s.id > best.id {
+            best = s;
+        }
+    }
+    best
+}
+
+/// Decodes a boarding pass like `FBFBBFFRLR`, which `parse` has already checked is seven of F/B then three of L/R.
+pub fn parse_seat(pass: &str) -> Seat {
+    // Each letter is a bit, B and R for 1, most significant first.
+    let bits = |letters: &str| letters.chars().fold(0, |n, c| 2 * n + i64::from(c == 'B' || c == 'R'));
+    let (row, col) = (bits(&pass[..7]), bits(&pass[7..]));
+    Seat { row, col, id: 8 * row + col }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seats() {
+        let seat = parse_seat("FBFBBFFRLR");
+        assert_eq!(Seat { row: 44, col: 5, id: 357 }, seat);
+        let seats = Day5.parse("BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n").unwrap();
+        assert_eq!(820, find_max_seats(&seats).id);
+    }
+
+    #[test]
+    fn test_bad_passes() {
+        let err = |data: &str| Day5.parse(data).map_err(|err| (err.line, err.col, err.expected)).unwrap_err();
+        assert_eq!((2, 3, String::from("F or B")), err("FBFBBFFRLR\nFBXBBFFRLR\n"));
+        assert_eq!((1, 8, String::from("L or R")), err("FBFBBFFBLR\n"));
+        assert_eq!((1, 6, String::from("a boarding pass of 10 letters")), err("FBFBB\n"));
+        assert_eq!((1, 11, String::from("a boarding pass of 10 letters")), err("FBFBBFFRLRR\n"));
+    }
+
+    #[test]
+    fn test_all() {
+        let seats = Day5.parse(include_str!("../../data/data5.txt")).unwrap();
+        assert_eq!((896, 659), (find_max_seats(&seats).id, find_missing_seat(&seats)));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Answer, ParseError, Rng, Solver};
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Vec<Vec<u32>>;
+
+    fn id(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Custom Customs"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_groups(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        anyone(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        everyone(input).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut groups = vec![];
+        for _ in 0..scale.unwrap_or(480) {
+            // Some questions everyone in the group answers, and some only a few do.
+            let shared: Vec<char> = (0..rng.below(8)).map(|_| rng.letter()).collect();
+            let mut group = String::new();
+            for _ in 0..rng.between(1, 5) {
+                let mut person: Vec<char> = shared.clone();
+                person.extend((0..rng.below(6)).map(|_| rng.letter()));
+                if person.is_empty() {
+                    person.push(rng.letter());
+                }
+                person.sort_unstable();
+                person.dedup();
+                rng.shuffle(&mut person);
+                group.extend(person);
+                group.push('\n');
+            }
+            groups.push(group);
+        }
+        Some(groups.join("\n"))
+    }
+}
+
+/// Each group's answers, one set of questions per person as a bit per letter.
+pub fn parse_groups(data: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let mut groups = vec![];
+    let mut group = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let mut ans_bin = 0_u32;
+        for (col, c) in line.char_indices() {
+            if !c.is_ascii_lowercase() {
+                return Err(ParseError::at_line(6, data, i + 1, col + 1, "a question (a to z)"));
+            }
+            ans_bin |= 1 << (u32::from(c) - u32::from('a'));
+        }
+
+        if ans_bin == 0 {
+            if !group.is_empty() {
+                groups.push(group);
+                group = vec![];
+            }
+        } else {
+            group.push(ans_bin);
+        }
+    }
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    Ok(groups)
+}
+
+/// The sum over all groups of the questions that anyone in the group answered "yes".
+pub fn anyone(groups: &[Vec<u32>]) -> u32 {
+    groups.iter().map(|g| g.iter().fold(0, |any, a| any | a).count_ones()).sum()
+}
+
+/// The sum over all groups of the questions that everyone in the group answered "yes".
+pub fn everyone(groups: &[Vec<u32>]) -> u32 {
+    groups.iter().map(|g| g.iter().fold(u32::MAX, |all, a| all & a).count_ones()).sum()
+}
@@ -0,0 +1,238 @@
+use pest::Parser;
+use pest_derive::Parser;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::params::Param;
+use crate::{params, Answer, ParseError, Rng, Solver};
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = BagRules;
+
+    fn id(&self) -> u32 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Handy Haversacks"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "bag", valid: params::reads_as::<String> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_bags(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        containers(input, &params::get(7, "bag", String::from("shiny gold"))).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        contents(input, &params::get(7, "bag", String::from("shiny gold"))).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let mut names: Vec<String> = ADJECTIVES
+            .iter()
+            .flat_map(|a| COLOURS.iter().map(move |c| format!("{} {}", a, c)))
+            .collect();
+        rng.shuffle(&mut names);
+        names.truncate(scale.unwrap_or(names.len()).max(1));
+        let mine = params::get(7, "bag", String::from("shiny gold"));
+        if !names.contains(&mine) {
+            names[0].clone_from(&mine);
+        }
+
+        // Bags only hold bags from the next couple of levels down, so the rules can't go round in circles, and the depth
+        // keeps the number of bags inside ours within reason.
+        let mut levels = vec![vec![]; LEVELS];
+        for name in &names {
+            let level = if *name == mine { LEVELS / 2 } else { rng.below(LEVELS) };
+            levels[level].push(name.as_str());
+        }
+
+        let mut rules = vec![];
+        for (level, bags) in levels.iter().enumerate() {
+            let deeper: Vec<&str> = levels[level + 1..LEVELS.min(level + 3)].iter().flatten().copied().collect();
+            for bag in bags {
+                let mut contents: Vec<String> = vec![];
+                if !deeper.is_empty() {
+                    let mut inner = deeper.clone();
+                    rng.shuffle(&mut inner);
+                    for name in inner.iter().take(1 + rng.below(5)) {
+                        let count = rng.between(1, 5);
+                        contents.push(format!("{} {} bag{}", count, name, if count == 1 { "" } else { "s" }));
+                    }
+                }
+                if contents.is_empty() {
+                    rules.push(format!("{} bags contain no other bags.", bag));
+                } else {
+                    rules.push(format!("{} bags contain {}.", bag, contents.join(", ")));
+                }
+            }
+        }
+        rng.shuffle(&mut rules);
+        Some(rules.join("\n") + "\n")
+    }
+}
+
+const ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored", "muted", "pale", "plaid",
+    "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const COLOURS: [&str; 33] = [
+    "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral", "crimson", "cyan", "fuchsia", "gold",
+    "gray", "green", "indigo", "lavender", "lime", "magenta", "maroon", "olive", "orange", "plum", "purple", "red",
+    "salmon", "silver", "tan", "teal", "tomato", "turquoise", "violet", "white", "yellow",
+];
+
+/// How deep generated bags nest.
+const LEVELS: usize = 10;
+
+/// What each bag type must hold, and how many of each.
+#[derive(Debug, Default, PartialEq)]
+pub struct BagRules {
+    contains: HashMap<String, Vec<(String, usize)>>,
+}
+
+pub fn parse_bags(data: &str) -> Result<BagRules, ParseError> {
+    let mut contains: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+
+    let parsed = DParser::parse(Rule::file, data)
+        .map_err(|e| ParseError::from_pest(7, data, &e))?
+        .next()
+        .unwrap();
+
+    for line in parsed.into_inner() {
+        let mut line_parts = line.into_inner();
+        // First part is the outer bag
+        let bag = line_parts.next();
+        let bag_name = match bag {
+            Some(x) => x.as_str(),
+            None => "",
+        };
+
+        for sub in line_parts {
+            if sub.as_rule() == Rule::somebags {
+                let mut bits = sub.into_inner();
+                let bag_bit = bits.next();
+                let count = bag_bit.unwrap().as_str().parse::<usize>().unwrap();
+                let what = bits.next().unwrap().as_str();
+                contains
+                    .entry(bag_name.to_string())
+                    .or_insert(vec![])
+                    .push((what.to_string(), count));
+            }
+        }
+    }
+    Ok(BagRules { contains })
+}
+
+/// The different bag types that end up holding `my_bag_type`.
+pub fn containers(rules: &BagRules, my_bag_type: &str) -> usize {
+    let contains = &rules.contains;
+    let mut contains_gold = HashSet::new();
+    let mut no_gold = HashSet::new();
+
+    for (bag, contents) in contains {
+        let mut working = vec![];
+        for (inner, _) in contents {
+            working.push(inner.as_str());
+        }
+
+        while !working.is_empty() {
+            let check = working.pop();
+            if check == Some(my_bag_type) {
+                // We've hit gold! Note and continue.
+                contains_gold.insert(bag.as_str());
+                break;
+            }
+
+            if let Some(c) = check {
+                if contains_gold.contains(&c) {
+                    // This bag already contains gold somewhere.
+                    contains_gold.insert(bag.as_str());
+                    break;
+                }
+
+                // We don't have to bother checking if we've already checked this bag has no gold.
+                if contains.contains_key(c) && !no_gold.contains(&c) {
+                    for (inner, _) in &contains[c] {
+                        working.push(inner);
+                    }
+                }
+            }
+
+            if working.is_empty() {
+                // This bag contains absolutely no gold.
+                no_gold.insert(bag.as_str());
+            }
+        }
+    }
+    contains_gold.len()
+}
+
+/// The total number of bags inside `my_bag_type`.
+pub fn contents(rules: &BagRules, my_bag_type: &str) -> usize {
+    let contains = &rules.contains;
+    let mut gold_count = 0;
+    let mut gold_working: Vec<(&str, usize)> = vec![];
+
+    for (bag, count) in contains.get(my_bag_type).into_iter().flatten() {
+        gold_working.push((bag, *count));
+    }
+
+    loop {
+        let mut repacked = vec![];
+        // We unpack each bag in turn.
+        for (bags, count) in &gold_working {
+            gold_count += count;
+
+            let sub_bags = contains.get(*bags);
+
+            if let Some(subs) = sub_bags {
+                for (ibag, icount) in subs {
+                    repacked.push((ibag.as_str(), *icount * count));
+                }
+            }
+        }
+
+        gold_working.clear();
+
+        for (bag, count) in repacked {
+            gold_working.push((bag, count));
+        }
+
+        if gold_working.is_empty() {
+            break;
+        }
+    }
+    gold_count
+}
+
+#[derive(Parser)]
+#[grammar = "parsers/day7.pest"]
+pub struct DParser {}
+
+#[allow(unused_imports)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data7.txt")
+    }
+
+    #[test]
+    fn test_all() {
+        let rules = parse_bags(data()).unwrap();
+        assert_eq!((169, 82372), (containers(&rules, "shiny gold"), contents(&rules, "shiny gold")));
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use crate::{input, Answer, ParseError, Rng, Solver};
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<Instruction>;
+
+    fn id(&self) -> u32 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Handheld Halting"
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        parse_fast(data)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        match run_program(input, false) {
+            ProgramResult::Looped(acc) | ProgramResult::Terminated(acc) => acc.into(),
+        }
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        match run_program(input, true) {
+            ProgramResult::Terminated(acc) => acc.into(),
+            ProgramResult::Looped(_) => Answer::from(-1),
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let program = generate_program(rng, scale.unwrap_or(600).max(4));
+        Some(
+            program
+                .iter()
+                .map(|ins| match *ins {
+                    Instruction::Jump(val, sign) => format!("jmp {}{}\n", if sign { '+' } else { '-' }, val),
+                    Instruction::Nop(val, sign) => format!("nop {}{}\n", if sign { '+' } else { '-' }, val),
+                    Instruction::Acc(val) => format!("acc {:+}\n", val),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The accumulator at the end of each way of running the boot code.
+#[derive(Debug, PartialEq)]
+pub struct BootResult {
+    /// Just before any instruction runs a second time.
+    pub at_loop: i64,
+    /// After the program terminates with one `jmp`/`nop` swapped, if any swap makes it terminate.
+    pub fixed: Option<i64>,
+}
+
+pub fn solve_program(code: &str) -> Result<BootResult, ParseError> {
+    let prog = parse_fast(code)?;
+
+    let loop_value = match run_program(&prog, false) {
+        ProgramResult::Looped(acc) | ProgramResult::Terminated(acc) => acc,
+    };
+
+    let fixed = match run_program(&prog, true) {
+        ProgramResult::Terminated(fixed) => Some(fixed),
+        ProgramResult::Looped(_) => None,
+    };
+    Ok(BootResult {
+        at_loop: loop_value,
+        fixed,
+    })
+}
+
+/// Reads the boot code, one `op ±arg` per line. Blank lines are skipped, but still count towards line numbers.
+pub fn parse_fast(code: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut program = vec![];
+
+    for (n, line) in input::lines(code) {
+        let error = |col, expected| ParseError::at_line(8, code, n, col, expected);
+
+        let (op, arg) = line.split_once(' ').ok_or_else(|| error(line.len() + 1, "an argument"))?;
+        let (unpacked, sign) = parse_signed(arg).map_err(|col| error(op.len() + 1 + col, "a signed number"))?;
+        let instruction = match op {
+            "nop" => Instruction::Nop(unpacked, sign),
+            "jmp" => Instruction::Jump(unpacked, sign),
+            "acc" => Instruction::Acc(arg.parse().map_err(|_| error(op.len() + 2, "a signed number"))?),
+            _ => return Err(error(1, "acc, jmp or nop")),
+        };
+        program.push(instruction);
+    }
+    Ok(program)
+}
+
+/// Makes up boot code of `len` instructions that loops, but terminates if exactly one `jmp`/`nop` is swapped.
+///
+/// The program is laid out in three blocks. The first runs every one of its lines in a shuffled order and ends at a
+/// `jmp` into the last block that's been broken into a `nop`. The last block runs straight through to the end. In
+/// between are traps which only ever jump back, so no other swap in the first block can escape it.
+fn generate_program(rng: &mut Rng, len: usize) -> Vec<Instruction> {
+    let first = len / 2;
+    let traps = 1 + len / 20;
+    let last = len - first - traps;
+
+    let jump = |from: usize, to: usize| {
+        if to >= from {
+            Instruction::Jump(to - from, true)
+        } else {
+            Instruction::Jump(from - to, false)
+        }
+    };
+    let acc = |rng: &mut Rng| Instruction::Acc(i64::try_from(rng.below(101)).unwrap() - 50);
+
+    let mut program = vec![Instruction::Nop(0, true); len];
+    let mut order: Vec<usize> = (1..first).collect();
+    rng.shuffle(&mut order);
+    order.insert(0, 0);
+    for pair in order.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        program[from] = if to != from + 1 {
+            jump(from, to)
+        } else if rng.chance(0.7) {
+            acc(rng)
+        } else {
+            // Swapped, this must land somewhere that still loops.
+            match jump(from, rng.below(first + traps)) {
+                Instruction::Jump(val, sign) => Instruction::Nop(val, sign),
+                ins => ins,
+            }
+        };
+    }
+    let broken = order[first - 1];
+    program[broken] = match jump(broken, first + traps) {
+        Instruction::Jump(val, sign) => Instruction::Nop(val, sign),
+        ins => ins,
+    };
+
+    for (trap, ins) in program.iter_mut().enumerate().take(first + traps).skip(first) {
+        *ins = jump(trap, rng.below(trap + 1));
+    }
+    for ins in &mut program[first + traps..] {
+        *ins = if rng.chance(0.7) { acc(rng) } else { Instruction::Nop(rng.below(last + 1), rng.chance(0.5)) };
+    }
+    program
+}
+
+/// Splits e.g. `+12` into `(12, true)`, or gives the column (1-based) where it went wrong.
+fn parse_signed(v: &str) -> Result<(usize, bool), usize> {
+    let sign = match v.chars().next() {
+        Some('+') => true,
+        Some('-') => false,
+        _ => return Err(1),
+    };
+    let value = v[1..].parse::<usize>().map_err(|_| 2_usize)?;
+    Ok((value, sign))
+}
+
+pub enum ProgramResult {
+    Looped(i64),
+    Terminated(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    Jump(usize, bool),
+    Nop(usize, bool),
+    Acc(i64),
+}
+
+/// Runs until the program loops or terminates. With `flip`, tries swapping each `jmp`/`nop` in turn until it terminates.
+pub fn run_program(program: &[Instruction], flip: bool) -> ProgramResult {
+    let seen: HashSet<usize> = HashSet::new();
+    let counter: usize = 0;
+    let acc = 0;
+    run_program_from(program, flip, &seen, counter, acc)
+}
+
+fn run_program_from(
+    program: &[Instruction],
+    flip: bool,
+    init_seen: &HashSet<usize>,
+    mut counter: usize,
+    mut acc: i64,
+) -> ProgramResult {
+    // Need to copy the loop breaker.
+    let mut seen: HashSet<usize> = init_seen.iter().copied().collect();
+
+    loop {
+        if seen.contains(&counter) {
+            break ProgramResult::Looped(acc);
+        }
+        seen.insert(counter);
+
+        if let Some(ins) = program.get(counter) {
+            match ins {
+                Instruction::Acc(val) => {
+                    acc += val;
+                    counter += 1;
+                }
+                Instruction::Jump(val, sign) => {
+                    if flip {
+                        // Try flipping to nop to get a result.
+                        if let terminal @ ProgramResult::Terminated(_) =
+                            run_program_from(program, false, &seen, counter + 1, acc)
+                        {
+                            break terminal;
+                        }
+                    }
+
+                    // Flip was unsuccesful, so continue.
+                    if *sign {
+                        counter = counter.saturating_add(*val);
+                    } else {
+                        counter = counter.saturating_sub(*val);
+                    }
+                }
+                Instruction::Nop(val, sign) => {
+                    if flip {
+                        // Try flipping to jump to get a result.
+                        let shifted = if *sign {
+                            counter.saturating_add(*val)
+                        } else {
+                            counter.saturating_sub(*val)
+                        };
+                        if let terminal @ ProgramResult::Terminated(_) =
+                            run_program_from(program, false, &seen, shifted, acc)
+                        {
+                            break terminal;
+                        }
+                    }
+
+                    // Flip was unsuccesful, so continue.
+                    counter += 1;
+                }
+            }
+        } else {
+            return ProgramResult::Terminated(acc);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data8.txt")
+    }
+
+    #[test]
+    fn test_all() {
+        assert_eq!(
+            BootResult {
+                at_loop: 1475,
+                fixed: Some(1270)
+            },
+            solve_program(data()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let program = generate_program(&mut Rng::new(seed), 50);
+            assert!(matches!(run_program(&program, false), ProgramResult::Looped(_)));
+
+            // Exactly one swap fixes it.
+            let fixes = (0..program.len())
+                .filter(|&i| {
+                    let mut swapped = program.clone();
+                    swapped[i] = match program[i] {
+                        Instruction::Jump(val, sign) => Instruction::Nop(val, sign),
+                        Instruction::Nop(val, sign) => Instruction::Jump(val, sign),
+                        Instruction::Acc(_) => return false,
+                    };
+                    matches!(run_program(&swapped, false), ProgramResult::Terminated(_))
+                })
+                .count();
+            assert_eq!(1, fixes);
+        }
+    }
+
+    #[test]
+    fn test_bad() {
+        let err = parse_fast("nop +0\nacc x12\njmp -1\n").unwrap_err();
+        assert_eq!(ParseError::new(8, 2, 5, "acc x12", "a signed number"), err);
+
+        let err = parse_fast("nop +0\n\nmul +2\n").unwrap_err();
+        assert_eq!(ParseError::new(8, 3, 1, "mul +2", "acc, jmp or nop"), err);
+
+        assert_eq!((1, 4), parse_fast("jmp").map_err(|e| (e.line, e.col)).unwrap_err());
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+
+use crate::params::Param;
+use crate::{params, Answer, ParseError, Rng, Solver};
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Vec<i64>;
+
+    fn id(&self) -> u32 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Encoding Error"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param { name: "window", valid: params::positive::<usize> },
+        ]
+    }
+
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+        data.lines()
+            .enumerate()
+            .filter(|(_, x)| !x.trim().is_empty())
+            .map(|(i, x)| x.trim().parse().map_err(|_| ParseError::at_line(9, data, i + 1, 1, "a number")))
+            .collect()
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        find_invalid(input, window()).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        // The weakness is found from the invalid number, so part 2 has to find that again.
+        find_weakness(input, find_invalid(input, window())).into()
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        let vals = generate_data(rng, scale.unwrap_or(1000), window());
+        Some(vals.iter().map(|v| v.to_string() + "\n").collect())
+    }
+}
+
+/// How many numbers back each number can be made from, which has to be some.
+fn window() -> usize {
+    params::get(9, "window", 25)
+}
+
+/// Makes up XMAS data of about `len` numbers, with one invalid number somewhere after the preamble of `window`.
+///
+/// Each valid number adds up two of the smaller numbers before it, which keeps the numbers from growing too quickly.
+/// They still double every `window` numbers or so, so very long data stops before it overflows.
+fn generate_data(rng: &mut Rng, len: usize, window: usize) -> Vec<i64> {
+    let len = len.max(window + 2);
+    let mut vals: Vec<i64> = (1..=50).collect();
+    rng.shuffle(&mut vals);
+    vals.truncate(window);
+
+    let mut invalid_at = window + 1 + rng.below(len - window - 1);
+    while vals.len() < len {
+        let recent = &vals[vals.len() - window..];
+        let is_sum = |v: i64| recent.iter().any(|&c| 2 * c != v && recent.contains(&(v - c)));
+        if vals.len() == invalid_at {
+            // A run of two or more numbers that doesn't happen to be a sum of two recent ones, or try again next time.
+            let found = (0..100)
+                .map(|_| {
+                    let start = rng.below(vals.len() - 1);
+                    let end = start + 2 + rng.below((vals.len() - start - 1).min(20));
+                    vals[start..end].iter().sum::<i64>()
+                })
+                .find(|&v| !is_sum(v));
+            if let Some(v) = found {
+                vals.push(v);
+                continue;
+            }
+            invalid_at += 1;
+        }
+
+        let mut smallest = recent.to_vec();
+        smallest.sort_unstable();
+        smallest.dedup();
+        smallest.truncate(8);
+        let (a, b) = loop {
+            let (a, b) = (*rng.pick(&smallest), *rng.pick(&smallest));
+            if a != b {
+                break (a, b);
+            }
+        };
+        match a.checked_add(b) {
+            Some(v) if v < i64::MAX / 4 => vals.push(v),
+            _ => break,
+        }
+    }
+    vals
+}
+
+/// The first number that isn't the sum of two of the `window` before it, or 0 if they all are.
+pub fn find_invalid(vals: &[i64], window: usize) -> i64 {
+    let mut checking = HashSet::new();
+    let mut invalid = 0;
+
+    for (i, val) in vals.iter().enumerate() {
+        if i < window {
+            checking.insert(val);
+        } else {
+            // Need to check previous 25 for sum.
+            let mut found = false;
+            for c in &vals[(i - window)..i] {
+                if ((2 * c) != *val) && checking.contains(&(val - c)) {
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                invalid = *val;
+                break;
+            }
+
+            // Otherwise we need to rotate our checking set.
+            checking.remove(&vals[i - window]);
+            checking.insert(val);
+        }
+    }
+    invalid
+}
+
+/// The sum of the smallest and largest numbers in the run that adds up to `invalid`, or 0 if there's no such run.
+pub fn find_weakness(vals: &[i64], invalid: i64) -> i64 {
+    // Search for range which sums to this.
+    let mut lower = 0;
+    let mut upper = 0;
+    let mut total = 0;
+
+    while upper < vals.len() {
+        if total == invalid {
+            // Now search for min/max values
+            let max = vals[lower..upper].iter().max().unwrap_or(&0);
+            let min = vals[lower..upper].iter().min().unwrap_or(&0);
+            return min + max;
+        }
+
+        if total < invalid {
+            total += vals[upper];
+            upper += 1;
+        } else {
+            total -= vals[lower];
+            lower += 1;
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn data() -> &'static str {
+        include_str!("../../data/data9.txt")
+    }
+
+    #[test]
+    fn test_all() {
+        let test_data = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+        let vals = Day9.parse(test_data).unwrap();
+        assert_eq!(127, find_invalid(&vals, 5));
+        assert_eq!(62, find_weakness(&vals, 127));
+
+        let vals = Day9.parse(data()).unwrap();
+        assert_eq!(675_280_050, find_invalid(&vals, 25));
+        assert_eq!(96_081_673, find_weakness(&vals, 675_280_050));
+    }
+}
@@ -0,0 +1,133 @@
+//! The solvers for each day. Each `dayN` module has a `DayN` implementing `Solver`, plus its working parts as public
+//! functions, so e.g. the Day 18 evaluator can be used on its own.
+#![warn(clippy::pedantic)]
+#![allow(clippy::uninlined_format_args)]
+// The days were written as a binary, where these don't apply; most of the panics are on malformed input.
+#![allow(clippy::must_use_candidate, clippy::missing_panics_doc, clippy::implicit_hasher)]
+// Every parser fails the same way, with a ParseError saying where.
+#![allow(clippy::missing_errors_doc)]
+use crate::Registry;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// Every solved day. New days only need adding here.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register(day1::Day1);
+    registry.register(day2::Day2);
+    registry.register(day3::Day3);
+    registry.register(day4::Day4);
+    registry.register(day5::Day5);
+    registry.register(day6::Day6);
+    registry.register(day7::Day7);
+    registry.register(day8::Day8);
+    registry.register(day9::Day9);
+    registry.register(day10::Day10);
+    registry.register(day11::Day11);
+    registry.register(day12::Day12);
+    registry.register(day13::Day13);
+    registry.register(day14::Day14);
+    registry.register(day15::Day15);
+    registry.register(day16::Day16);
+    registry.register(day17::Day17);
+    registry.register(day18::Day18);
+    registry.register(day19::Day19);
+    registry.register(day20::Day20);
+    registry.register(day21::Day21);
+    registry.register(day22::Day22);
+    registry.register(day23::Day23);
+    registry.register(day24::Day24);
+    registry.register(day25::Day25);
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parts, Rng};
+
+    #[test]
+    fn test_generate() {
+        let registry = registry();
+        for id in registry.ids() {
+            let puzzle = registry.get(id).unwrap();
+            // The smallest scales too, which some generators have to stretch to.
+            for (seed, scale) in (0..3).map(|seed| (seed, 12)).chain([(0, 1), (1, 2)]) {
+                let input = puzzle.generate(&mut Rng::new(seed), Some(scale)).unwrap();
+                assert_eq!(Some(&input), puzzle.generate(&mut Rng::new(seed), Some(scale)).as_ref());
+                // Part 2 is too slow for some days in a debug build, but part 1 still needs the input to parse.
+                if let Err(e) = puzzle.solve_timed(&input, Parts::One) {
+                    panic!("Day {} generated an input that doesn't parse (seed {}, scale {}): {}", id, seed, scale, e);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reference() {
+        // The days whose optimised solvers have a plain version to check them against.
+        const CHECKED: [u32; 4] = [11, 15, 17, 23];
+        let registry = registry();
+        let mut checked = vec![];
+        for puzzle in registry.iter() {
+            for seed in 0..6 {
+                let input = puzzle.generate(&mut Rng::new(seed), Some(8)).unwrap();
+                for (part, actual, expected) in puzzle.cross_check(&input).unwrap() {
+                    assert_eq!(
+                        expected,
+                        actual,
+                        "Day {} part {} disagrees with its reference (seed {}) on:\n{}",
+                        puzzle.id(),
+                        part,
+                        seed,
+                        input
+                    );
+                    checked.push(puzzle.id());
+                }
+            }
+        }
+        for id in &CHECKED {
+            assert!(checked.contains(id), "Day {} has no reference to check against", id);
+        }
+    }
+
+    #[test]
+    fn test_saved_elsewhere() {
+        // The same input as another editor might save it: a byte order mark, CRLF line endings, trailing spaces and no
+        // newline at the end.
+        let registry = registry();
+        for puzzle in registry.iter() {
+            let input = puzzle.generate(&mut Rng::new(1), Some(8)).unwrap();
+            let mangled = String::from("\u{feff}") + &input.trim_end().replace('\n', " \t\r\n");
+            let (expected, _) = puzzle.solve_timed(&input, Parts::One).unwrap();
+            match puzzle.solve_timed(&mangled, Parts::One) {
+                Ok((actual, _)) => assert_eq!(expected, actual, "Day {} read {:?} differently", puzzle.id(), mangled),
+                Err(e) => panic!("Day {} couldn't read {:?}: {}", puzzle.id(), mangled, e),
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::error::Error;
+use std::fmt;
+
+use pest::error::{ErrorVariant, InputLocation};
+use pest::RuleType;
+
+/// Where and why a day's input couldn't be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub col: usize,
+    /// The whole of the offending line.
+    pub text: String,
+    /// What we were hoping to find there, e.g. "a number".
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u32, line: usize, col: usize, text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line,
+            col,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at byte `offset` into `input`.
+    pub fn at(day: u32, input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+
+        ParseError::new(
+            day,
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            input[line_start..line_end].trim_end_matches('\r'),
+            expected,
+        )
+    }
+
+    /// An error at `col` (1-based) of line `line` (also 1-based) of `input`.
+    pub fn at_line(day: u32, input: &str, line: usize, col: usize, expected: impl Into<String>) -> Self {
+        let text = input.lines().nth(line.saturating_sub(1)).unwrap_or("");
+        ParseError::new(day, line, col, text, expected)
+    }
+
+    /// Converts an error from one of the pest grammars, where `input` is what was being parsed.
+    pub fn from_pest<R: RuleType>(day: u32, input: &str, err: &pest::error::Error<R>) -> Self {
+        let expected = match &err.variant {
+            ErrorVariant::ParsingError { positives, .. } if !positives.is_empty() => positives
+                .iter()
+                .map(|r| format!("{:?}", r))
+                .collect::<Vec<_>>()
+                .join(" or "),
+            ErrorVariant::ParsingError { .. } => String::from("something else"),
+            ErrorVariant::CustomError { message } => message.clone(),
+        };
+        let offset = match err.location {
+            InputLocation::Pos(pos) | InputLocation::Span((pos, _)) => pos,
+        };
+        ParseError::at(day, input, offset, expected)
+    }
+
+    /// Moves an error found in a single line to where that line is in the whole input, for parsers that work a line at
+    /// a time.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// A compiler-style diagnostic with a caret under the problem, e.g.
+    ///
+    /// ```text
+    /// error: expected a number
+    ///  --> day 8, line 3, column 5
+    ///   |
+    /// 3 | acc x12
+    ///   |     ^
+    /// ```
+    pub fn render(&self) -> String {
+        self.render_in(&format!("day {}", self.day))
+    }
+
+    /// As `render`, but saying the input is `source` rather than a day's, e.g. a pattern file for `life`.
+    pub fn render_in(&self, source: &str) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Tabs are kept so the caret lines up under them, whatever the terminal's tab width.
+        let indent: String = self
+            .text
+            .chars()
+            .take(self.col.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!(
+            "error: expected {}\n{}--> {}, line {}, column {}\n{} |\n{} | {}\n{} | {}^",
+            self.expected, gutter, source, self.line, self.col, gutter, number, self.text, gutter, indent
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.col, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "nop +0\nacc x12\njmp -1\n";
+        let err = ParseError::at(8, input, 11, "a number");
+        assert_eq!(ParseError::new(8, 2, 5, "acc x12", "a number"), err);
+        assert_eq!("day 8, line 2, column 5: expected a number", err.to_string());
+        assert_eq!(
+            "error: expected a number\n --> day 8, line 2, column 5\n  |\n2 | acc x12\n  |     ^",
+            err.render()
+        );
+        assert!(err.render_in("glider.txt").contains(" --> glider.txt, line 2, column 5\n"));
+
+        assert_eq!(err, ParseError::at_line(8, input, 2, 5, "a number"));
+        assert_eq!(ParseError::new(8, 4, 1, "", "more"), ParseError::at(8, input, 100, "more"));
+    }
+}
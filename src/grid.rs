@@ -0,0 +1,324 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{input, ParseError};
+
+/// The four steps to the cells sharing an edge, as `(rows, columns)`.
+pub const STEPS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+/// The eight steps to the cells sharing an edge or a corner, as `(rows, columns)`.
+pub const STEPS8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Something a map is drawn with, one character each, like the seats (`L`) and floor (`.`) of Day 11.
+pub trait Cell: Sized {
+    /// What the characters should have been when one isn't, e.g. "# or .".
+    const EXPECTED: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+/// `#` for true and `.` for false, as most of the puzzles draw things.
+impl Cell for bool {
+    const EXPECTED: &'static str = "# or .";
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl Cell for char {
+    const EXPECTED: &'static str = "a character";
+
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// A rectangular map, like Day 3's forest or one of Day 20's tiles, addressed by `(row, column)` from the top left.
+///
+/// Cells are kept row by row in one `Vec`, so a cell can also be known by its place in that, as `flat` gives it, which
+/// is handy for keeping things like neighbour lists alongside.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid2D<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid2D<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid2D {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid of the given rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == width), "Rows of different lengths");
+        Grid2D {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Reads a map a character per cell, one row per line. Blank lines are skipped, but every other line must be the
+    /// same length.
+    pub fn parse(day: u32, data: &str) -> Result<Self, ParseError>
+    where
+        T: Cell,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (n, line) in input::lines(data) {
+            let mut row_width = 0;
+            for (i, c) in line.chars().enumerate() {
+                let cell = T::from_char(c).ok_or_else(|| ParseError::at_line(day, data, n, i + 1, T::EXPECTED))?;
+                cells.push(cell);
+                row_width += 1;
+            }
+            match width {
+                Some(width) if width != row_width => {
+                    let expected = format!("a row of {} cells, like the first", width);
+                    return Err(ParseError::at_line(day, data, n, row_width.min(width) + 1, expected));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) => Ok(Grid2D { width, height, cells }),
+            None => Err(ParseError::at(day, data, 0, "a map")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at `(row, col)`, or `None` if that's off the map.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `(row, col)` if the map repeated forever in every direction, like Day 3's forest does to the right.
+    pub fn wrapping(&self, row: isize, col: isize) -> &T {
+        let row = row.rem_euclid(self.height as isize) as usize;
+        let col = col.rem_euclid(self.width as isize) as usize;
+        &self.cells[row * self.width + col]
+    }
+
+    /// Where `(row, col)` is in `cells`.
+    pub fn flat(&self, row: usize, col: usize) -> usize {
+        row * self.width + col
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell with its `(row, col)`, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    /// The cell one `step` away from `(row, col)`, if it's on the map.
+    pub fn step(&self, (row, col): (usize, usize), (drow, dcol): (isize, isize)) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(drow)?;
+        let col = col.checked_add_signed(dcol)?;
+        (row < self.height && col < self.width).then_some((row, col))
+    }
+
+    /// The cells sharing an edge with `(row, col)`.
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        STEPS4.iter().filter_map(move |&step| self.step((row, col), step))
+    }
+
+    /// The cells sharing an edge or a corner with `(row, col)`.
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        STEPS8.iter().filter_map(move |&step| self.step((row, col), step))
+    }
+
+    /// The first cell in each of the eight directions from `(row, col)` that can't be seen through, like the seats
+    /// visible across Day 11's floor.
+    pub fn in_sight<'a>(
+        &'a self,
+        row: usize,
+        col: usize,
+        see_through: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        STEPS8.iter().filter_map(move |&step| {
+            let mut at = self.step((row, col), step)?;
+            while see_through(&self[at]) {
+                at = self.step(at, step)?;
+            }
+            Some(at)
+        })
+    }
+
+    /// The same map with each cell changed by `f`.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid2D<U> {
+        Grid2D {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The map turned a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotated(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|col| (0..self.height).rev().map(move |row| (row, col)))
+            .map(|at| self[at].clone())
+            .collect();
+        Grid2D {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// The map flipped top to bottom.
+    pub fn flipped(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.rows().rev().flatten().cloned().collect();
+        Grid2D {
+            width: self.width,
+            height: self.height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is off a {}x{} map", row, col, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is off a {}x{} map", row, col, width, height))
+    }
+}
+
+/// The map as it would be read, a line per row.
+impl<T: Cell> fmt::Display for Grid2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(Cell::to_char).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid2D<char> {
+        Grid2D::parse(99, text).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let map: Grid2D<bool> = Grid2D::parse(3, "..#\n#..\n\n").unwrap();
+        assert_eq!((3, 2), (map.width(), map.height()));
+        assert_eq!((Some(&true), Some(&false), None), (map.get(0, 2), map.get(1, 1), map.get(2, 0)));
+        assert_eq!("..#\n#..\n", map.to_string());
+
+        let err = Grid2D::<bool>::parse(3, "..#\n#.L\n").unwrap_err();
+        assert_eq!((2, 3, "# or ."), (err.line, err.col, err.expected.as_str()));
+        let err = Grid2D::<bool>::parse(3, "..#\n#.\n").unwrap_err();
+        assert_eq!((2, 3), (err.line, err.col));
+        assert!(Grid2D::<bool>::parse(3, "\n").is_err());
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let map = grid("ab\ncd\n");
+        assert_eq!((&'b', &'c', &'d'), (map.wrapping(0, 5), map.wrapping(-1, 2), map.wrapping(3, -1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let map = grid("abc\ndef\nghi\n");
+        let at = |cells: Vec<(usize, usize)>| cells.into_iter().map(|cell| map[cell]).collect::<String>();
+        assert_eq!("bdfh", at(map.neighbours4(1, 1).collect()));
+        assert_eq!("abcdfghi", at(map.neighbours8(1, 1).collect()));
+        assert_eq!("bde", at(map.neighbours8(0, 0).collect()));
+
+        let floor = grid("L.L.\n....\nL.#.\n");
+        let seen: Vec<_> = floor.in_sight(0, 0, |&c| c == '.').collect();
+        assert_eq!(vec![(0, 2), (2, 0), (2, 2)], seen);
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let map = grid("abc\ndef\n");
+        assert_eq!("da\neb\nfc\n", map.rotated().to_string());
+        assert_eq!("def\nabc\n", map.flipped().to_string());
+        assert_eq!(map, map.rotated().rotated().rotated().rotated());
+    }
+
+    #[test]
+    fn test_iter_and_map() {
+        let map = grid("ab\ncd\n");
+        assert_eq!(Some(((1, 0), &'c')), map.iter().nth(2));
+        assert_eq!(3, map.flat(1, 1));
+        assert_eq!("#.\n..\n", map.map(|&c| c == 'a').to_string());
+        assert_eq!(map, Grid2D::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']]));
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::bench::Stats;
+
+const HEADER: &str = "revision,timestamp,day,phase,runs,min_ns,median_ns,mean_ns,p95_ns,params,input";
+/// The header of history files started before the parameters were recorded.
+const OLD_HEADER: &str = "revision,timestamp,day,phase,runs,min_ns,median_ns,mean_ns,p95_ns,input";
+
+/// One benchmarked phase of one day, as kept in the history file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// The git revision benchmarked, with `-dirty` on the end if there were uncommitted changes.
+    pub revision: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub day: u32,
+    /// `parse`, `part1` or `part2`.
+    pub phase: String,
+    pub stats: Stats,
+    /// The fingerprint of the day's parameters (see `params::fingerprint`), or `None` in an old history file that
+    /// didn't record them.
+    pub params: Option<u64>,
+    /// The input file, or `-` for stdin.
+    pub input: String,
+}
+
+impl Entry {
+    pub fn new(revision: &str, day: u32, phase: &str, stats: Stats, params: u64, input: Option<&Path>) -> Entry {
+        Entry {
+            revision: revision.to_string(),
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            day,
+            phase: phase.to_string(),
+            stats,
+            params: Some(params),
+            input: input.map_or_else(|| String::from("-"), |p| p.display().to_string()),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}{}",
+            self.revision,
+            self.timestamp,
+            self.day,
+            self.phase,
+            self.stats.runs,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.mean.as_nanos(),
+            self.stats.p95.as_nanos(),
+            self.params.map(|hash| format!("{:016x},", hash)).unwrap_or_default(),
+            self.input
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Entry> {
+        // The input comes last so that it can have commas in it.
+        let fields: Vec<&str> = line.splitn(10, ',').collect();
+        if fields.len() != 10 {
+            return None;
+        }
+        // Lines added before the parameters were recorded go straight on to the input, even once newer lines follow
+        // them in the same file.
+        let (params, input) = match fields[9].split_once(',') {
+            Some((hash, input)) if hash.len() == 16 => (Some(u64::from_str_radix(hash, 16).ok()?), input),
+            _ => (None, fields[9]),
+        };
+        let nanos = |i: usize| fields[i].parse().ok().map(Duration::from_nanos);
+        Some(Entry {
+            revision: fields[0].to_string(),
+            timestamp: fields[1].parse().ok()?,
+            day: fields[2].parse().ok()?,
+            phase: fields[3].to_string(),
+            stats: Stats {
+                runs: fields[4].parse().ok()?,
+                min: nanos(5)?,
+                median: nanos(6)?,
+                mean: nanos(7)?,
+                p95: nanos(8)?,
+            },
+            params,
+            input: input.to_string(),
+        })
+    }
+}
+
+/// Every benchmark run so far, oldest first, read from a CSV file like `bench-history.csv`.
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Reads the history file, which is empty if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<History, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => History::parse(&text).map_err(|err| format!("{}: {}", path.display(), err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(format!("Could not read {}: {}", path.display(), err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<History, String> {
+        let mut entries = vec![];
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line == HEADER || line == OLD_HEADER {
+                continue;
+            }
+            match Entry::from_csv(line) {
+                Some(entry) => entries.push(entry),
+                None => return Err(format!("line {}: expected {}", i + 1, HEADER)),
+            }
+        }
+        Ok(History { entries })
+    }
+
+    /// Adds entries to the end of the history file, starting it if need be.
+    pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+        let new = !path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if new {
+            writeln!(file, "{}", HEADER)?;
+        }
+        for entry in entries {
+            writeln!(file, "{}", entry.to_csv())?;
+        }
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The most recently benchmarked revision.
+    pub fn latest_revision(&self) -> Option<&str> {
+        self.entries.last().map(|e| e.revision.as_str())
+    }
+
+    /// The full name of a revision in the history, given any prefix of it, or `None` if there isn't exactly one.
+    pub fn find_revision(&self, prefix: &str) -> Option<&str> {
+        let mut found: Vec<&str> = self
+            .entries
+            .iter()
+            .map(|e| e.revision.as_str())
+            .filter(|r| r.starts_with(prefix))
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        // An exact match wins over e.g. its `-dirty` version.
+        match found.as_slice() {
+            [only] => Some(only),
+            _ => found.into_iter().find(|r| *r == prefix),
+        }
+    }
+
+    /// The revision benchmarked most recently before `revision`, to compare it against by default.
+    pub fn previous_revision(&self, revision: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .map(|e| e.revision.as_str())
+            .find(|r| *r != revision)
+    }
+
+    /// The latest timing of each day and phase benchmarked at both revisions, on the same input and with the same
+    /// parameters, since e.g. Day 15 with fewer turns isn't any faster for the code being better.
+    pub fn compare(&self, baseline: &str, current: &str) -> Vec<Comparison> {
+        let latest = |revision: &str| -> HashMap<(u32, &str, &str, Option<u64>), &Entry> {
+            self.entries
+                .iter()
+                .filter(|e| e.revision == revision)
+                .map(|e| ((e.day, e.phase.as_str(), e.input.as_str(), e.params), e))
+                .collect()
+        };
+        let before = latest(baseline);
+        let mut comparisons: Vec<Comparison> = latest(current)
+            .into_iter()
+            .filter_map(|(key, after)| {
+                before.get(&key).map(|before| Comparison {
+                    day: after.day,
+                    phase: after.phase.clone(),
+                    baseline: before.stats.median,
+                    current: after.stats.median,
+                })
+            })
+            .collect();
+        comparisons.sort_by(|a, b| (a.day, &a.phase).cmp(&(b.day, &b.phase)));
+        comparisons
+    }
+}
+
+/// How the median time of one phase of one day changed between two revisions.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub phase: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// The change as a percentage of the baseline, positive when it got slower.
+    pub fn change_pct(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    /// Whether it got more than `threshold` percent slower. Phases quick enough to be lost in the timer's noise only
+    /// count if they slowed by at least a microsecond.
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change_pct() > threshold && self.current.saturating_sub(self.baseline) >= Duration::from_micros(1)
+    }
+}
+
+/// The revision of the working tree, e.g. `3142aaa` or `3142aaa-dirty`, or `unknown` outside a git checkout.
+pub fn git_revision() -> String {
+    let git = |args: &[&str]| {
+        process::Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(revision) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if !changes.is_empty() => format!("{}-dirty", revision),
+            _ => revision,
+        },
+        None => String::from("unknown"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(revision: &str, day: u32, phase: &str, median_us: u64) -> Entry {
+        entry_with(revision, day, phase, median_us, 0x1234)
+    }
+
+    fn entry_with(revision: &str, day: u32, phase: &str, median_us: u64, params: u64) -> Entry {
+        let d = Duration::from_micros(median_us);
+        let stats = Stats {
+            runs: 10,
+            min: d,
+            median: d,
+            mean: d,
+            p95: d,
+        };
+        Entry::new(revision, day, phase, stats, params, Some(Path::new("data/data15.txt")))
+    }
+
+    #[test]
+    fn test_csv() {
+        let e = entry("abc1234", 15, "part2", 1500);
+        let line = e.to_csv();
+        assert!(line.starts_with("abc1234,"));
+        assert!(line.ends_with(",15,part2,10,1500000,1500000,1500000,1500000,0000000000001234,data/data15.txt"));
+        assert_eq!(Some(e.clone()), Entry::from_csv(&line));
+
+        let history = History::parse(&format!("{}\n{}\n", HEADER, line)).unwrap();
+        assert_eq!(1, history.entries().len());
+        assert!(History::parse("abc1234,1,2,3").is_err());
+
+        // A file from before the parameters were recorded still reads, as entries without them, and can be added to.
+        let old = "abc1234,1,15,part2,10,1500000,1500000,1500000,1500000,data/data15.txt";
+        let history = History::parse(&format!("{}\n{}\n{}\n", OLD_HEADER, old, line)).unwrap();
+        let old = Entry { params: None, timestamp: 1, ..e.clone() };
+        assert_eq!(old.to_csv(), "abc1234,1,15,part2,10,1500000,1500000,1500000,1500000,data/data15.txt");
+        assert_eq!([old, e], history.entries());
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = History {
+            entries: vec![
+                entry("aaa", 15, "part2", 1000),
+                entry("aaa", 23, "part2", 1000),
+                entry("bbb", 15, "part2", 900),
+                entry("bbb", 23, "part2", 1000),
+                entry("bbb-dirty", 15, "part2", 1200),
+                entry("bbb-dirty", 17, "part1", 50),
+                entry("bbb-dirty", 23, "part2", 950),
+            ],
+        };
+        assert_eq!(Some("bbb-dirty"), history.latest_revision());
+        assert_eq!(Some("bbb"), history.previous_revision("bbb-dirty"));
+        assert_eq!(Some("bbb"), history.find_revision("bbb"));
+        assert_eq!(Some("bbb-dirty"), history.find_revision("bbb-d"));
+        assert_eq!(None, history.find_revision("ccc"));
+
+        let comparisons = history.compare("bbb", "bbb-dirty");
+        assert_eq!(vec![15, 23], comparisons.iter().map(|c| c.day).collect::<Vec<_>>());
+        assert!((comparisons[0].change_pct() - 33.333).abs() < 0.01);
+        assert!(comparisons[0].regressed(10.0));
+        assert!(!comparisons[1].regressed(10.0));
+
+        // Timings with other parameters aren't comparable.
+        let history = History {
+            entries: vec![
+                entry_with("aaa", 15, "part2", 1000, 1),
+                entry_with("aaa", 23, "part2", 1000, 1),
+                entry_with("bbb", 15, "part2", 10, 2),
+                entry_with("bbb", 23, "part2", 900, 1),
+            ],
+        };
+        assert_eq!(vec![23], history.compare("aaa", "bbb").iter().map(|c| c.day).collect::<Vec<_>>());
+
+        let tiny = Comparison {
+            day: 5,
+            phase: String::from("parse"),
+            baseline: Duration::from_nanos(110),
+            current: Duration::from_nanos(400),
+        };
+        assert!(!tiny.regressed(10.0));
+    }
+}
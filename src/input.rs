@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+/// Puzzle input as the days expect it, however it was saved: no byte order mark, `\n` line endings, nothing trailing
+/// at the end of a line, and exactly one newline at the end unless there's nothing at all.
+///
+/// Every day's input goes through this before it's parsed, so files from other editors and platforms read the same as
+/// the ones downloaded. Input that's already clean is passed straight through.
+pub fn normalise(data: &str) -> Cow<'_, str> {
+    if is_clean(data) {
+        return Cow::Borrowed(data);
+    }
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+    let mut clean = String::with_capacity(data.len() + 1);
+    for line in data.split('\n') {
+        clean.push_str(line.trim_end());
+        clean.push('\n');
+    }
+    let end = clean.trim_end().len();
+    clean.truncate(end);
+    if !clean.is_empty() {
+        clean.push('\n');
+    }
+    Cow::Owned(clean)
+}
+
+fn is_clean(data: &str) -> bool {
+    data.is_empty()
+        || (!data.starts_with('\u{feff}')
+            && data.ends_with('\n')
+            && !data.ends_with("\n\n")
+            && !data.contains('\r')
+            && data.lines().all(|line| line.len() == line.trim_end().len()))
+}
+
+/// The lines with anything on them, each with its line number (1-based, as `ParseError::at_line` wants it), for the
+/// days that read their input a line at a time.
+pub fn lines(data: &str) -> impl Iterator<Item = (usize, &str)> {
+    data.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!("nop +0\nacc +1\n", normalise("\u{feff}nop +0\r\nacc +1\r\n"));
+        assert_eq!("nop +0\nacc +1\n", normalise("nop +0  \nacc +1"));
+        assert_eq!("abc\n\nab\n", normalise("abc\t\r\n \r\nab\n\n\n"));
+        assert_eq!("  #.\n", normalise("  #.\r\n"));
+        assert_eq!("", normalise(" \r\n"));
+        assert_eq!("", normalise(""));
+    }
+
+    #[test]
+    fn test_clean_borrowed() {
+        assert!(matches!(normalise("F10\nN3\n"), Cow::Borrowed(_)));
+        assert!(matches!(normalise("F10\nN3"), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(vec![(1, "F10"), (3, "N3")], lines("F10\n \nN3  \n\n").collect::<Vec<_>>());
+    }
+}
@@ -1,28 +1,46 @@
-use std::env;
+#![allow(clippy::uninlined_format_args)]
 use std::time;
 use termion::{color, style};
 
-pub struct Config {
-    pub filename: String,
-}
-
-impl Config {
-    pub fn new(mut args: env::Args) -> Result<Config, &'static str> {
-        args.next(); // Skip filename
-        args.next(); // Skip day index
-
-        let filename = match args.next() {
-            Some(arg) => arg,
-            None => return Err("No filename given"),
-        }
-;
+mod answers;
+mod bench;
+pub mod cancel;
+pub mod checkpoint;
+mod config;
+pub mod days;
+mod error;
+pub mod grid;
+mod history;
+pub mod input;
+pub mod life;
+pub mod log;
+mod output;
+pub mod params;
+mod pool;
+pub mod progress;
+mod report;
+mod rng;
+pub mod scaffold;
+mod solver;
+pub mod tui;
 
-        Ok(Config { filename, })
-    }
-}
+pub use answers::{Check, Manifest};
+pub use bench::{fmt_micros, Samples, Stats};
+pub use cancel::{Cancel, Stopped};
+pub use config::{Command, Config, Source};
+pub use error::ParseError;
+pub use grid::Grid2D;
+pub use history::{git_revision, Comparison, Entry, History};
+pub use log::{capture, Level};
+pub use pool::{default_jobs, map_parallel};
+pub use report::{input_hash, render_report, ReportEntry};
+pub use params::Params;
+pub use output::{json_string, BenchRecord, CompareRecord, ErrorRecord, Format, Record, VerifyRecord};
+pub use rng::Rng;
+pub use solver::{Answer, Parts, Puzzle, Registry, Solution, Solver, Timings};
 
-pub fn print_day(d: i32) {
-    println!(" \u{1F389} {}Day {} !{}", style::Underline, d, style::Reset);
+pub fn print_day(d: u32, title: &str) {
+    println!(" \u{1F389} {}Day {} !{} {}", style::Underline, d, style::Reset, title);
 }
 
 pub fn crab() -> String {
@@ -0,0 +1,454 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use crate::grid::{Grid2D, STEPS4, STEPS8};
+
+/// Which cells there are and which are each other's neighbours, for a Life-like automaton to run on.
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+
+    /// The cells whose states count towards `cell`'s next one.
+    fn neighbours(&self, cell: Self::Cell) -> impl Iterator<Item = Self::Cell> + '_;
+}
+
+/// A topology with a fixed set of cells numbered from zero, which `Dense` can keep in a flat array.
+pub trait Finite: Topology<Cell = usize> {
+    /// How many cells there could be, counting any that never change.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The cells that follow the rule. Any others stay as they start.
+    fn cells(&self) -> &[usize];
+}
+
+/// The infinite square grid of `(row, col)` cells, with the eight cells around each as its neighbours or, without
+/// `diagonals`, just the four sharing an edge.
+#[derive(Debug, Clone, Copy)]
+pub struct Square {
+    pub diagonals: bool,
+}
+
+impl Topology for Square {
+    type Cell = (i64, i64);
+
+    fn neighbours(&self, (row, col): (i64, i64)) -> impl Iterator<Item = (i64, i64)> + '_ {
+        let steps: &[(isize, isize)] = if self.diagonals { &STEPS8 } else { &STEPS4 };
+        steps.iter().map(move |&(drow, dcol)| (row + drow as i64, col + dcol as i64))
+    }
+}
+
+/// The infinite grid of hexagons, at axial `(x, y)` coordinates: `x` goes east, and `y` north-east, so north-west is
+/// `(0, 1)` and south-east `(0, -1)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Hex;
+
+const HEX_STEPS: [(i64, i64); 6] = [(1, 0), (-1, 0), (1, 1), (-1, -1), (0, 1), (0, -1)];
+
+impl Topology for Hex {
+    type Cell = (i64, i64);
+
+    fn neighbours(&self, (x, y): (i64, i64)) -> impl Iterator<Item = (i64, i64)> + '_ {
+        HEX_STEPS.iter().map(move |&(dx, dy)| (x + dx, y + dy))
+    }
+}
+
+/// The infinite `N` dimensional lattice, each cell touching the `3^N - 1` around it, edges and corners alike.
+#[derive(Debug, Clone)]
+pub struct Lattice<const N: usize> {
+    steps: Vec<[i64; N]>,
+}
+
+impl<const N: usize> Lattice<N> {
+    pub fn new() -> Self {
+        // Every step to a neighbour, as the digits of a number in base 3.
+        let count = (0..N).fold(1, |count, _| count * 3);
+        let steps = (0..count)
+            .map(|mut n| {
+                let mut step = [0; N];
+                for d in &mut step {
+                    *d = n % 3 - 1;
+                    n /= 3;
+                }
+                step
+            })
+            .filter(|step| step.iter().any(|&d| d != 0))
+            .collect();
+        Lattice { steps }
+    }
+}
+
+impl<const N: usize> Default for Lattice<N> {
+    fn default() -> Self {
+        Lattice::new()
+    }
+}
+
+impl<const N: usize> Topology for Lattice<N> {
+    type Cell = [i64; N];
+
+    fn neighbours(&self, cell: [i64; N]) -> impl Iterator<Item = [i64; N]> + '_ {
+        self.steps.iter().map(move |step| {
+            let mut next = cell;
+            for (c, d) in next.iter_mut().zip(step) {
+                *c += d;
+            }
+            next
+        })
+    }
+}
+
+/// Any fixed set of cells, with everyone's neighbours worked out up front, like the seats on Day 11 that can see each
+/// other across the floor.
+#[derive(Debug, Clone)]
+pub struct Fixed {
+    neighbours: Vec<Vec<usize>>,
+    cells: Vec<usize>,
+}
+
+impl Fixed {
+    /// The cells of `grid` that `is_cell` picks, each with the neighbours `neighbours` gives it.
+    pub fn from_grid<T, N>(
+        grid: &Grid2D<T>,
+        is_cell: impl Fn(&T) -> bool,
+        neighbours: impl Fn(usize, usize) -> N,
+    ) -> Self
+    where
+        N: Iterator<Item = (usize, usize)>,
+    {
+        let mut fixed = Fixed {
+            neighbours: vec![vec![]; grid.cells().len()],
+            cells: vec![],
+        };
+        for ((row, col), cell) in grid.iter() {
+            if is_cell(cell) {
+                let at = grid.flat(row, col);
+                fixed.cells.push(at);
+                fixed.neighbours[at] = neighbours(row, col).map(|(r, c)| grid.flat(r, c)).collect();
+            }
+        }
+        fixed
+    }
+}
+
+impl Topology for Fixed {
+    type Cell = usize;
+
+    fn neighbours(&self, cell: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbours[cell].iter().copied()
+    }
+}
+
+impl Finite for Fixed {
+    fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+}
+
+/// Which counts of live neighbours bring a dead cell to life, and which keep a live one alive, written as e.g.
+/// `B3/S23` for Conway's Life. Counts over 9 can be given with commas, e.g. `B3/S2,3,10`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    birth: u128,
+    survival: u128,
+}
+
+impl Rule {
+    pub const CONWAY: Rule = Rule { birth: 1 << 3, survival: 1 << 2 | 1 << 3 };
+
+    /// Counts up to 127 neighbours.
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let bits = |counts: &[usize]| {
+            counts.iter().fold(0, |bits, &n| bits | 1_u128.checked_shl(n as u32).unwrap_or(0))
+        };
+        Rule {
+            birth: bits(birth),
+            survival: bits(survival),
+        }
+    }
+
+    pub fn born(self, neighbours: usize) -> bool {
+        neighbours < 128 && self.birth & 1 << neighbours != 0
+    }
+
+    pub fn survives(self, neighbours: usize) -> bool {
+        neighbours < 128 && self.survival & 1 << neighbours != 0
+    }
+
+    /// Whether a cell is alive next time round, given whether it is now and how many of its neighbours are.
+    pub fn next(self, alive: bool, neighbours: usize) -> bool {
+        if alive {
+            self.survives(neighbours)
+        } else {
+            self.born(neighbours)
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expected = || format!("expected a rule like B3/S23, not {}", s);
+        let (birth, survival) = s.split_once('/').ok_or_else(expected)?;
+        let counts = |part: &str, prefix: char| -> Result<Vec<usize>, String> {
+            let part = part.strip_prefix(prefix).or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()));
+            let part = part.ok_or_else(expected)?;
+            let counts: Option<Vec<usize>> = if part.contains(',') {
+                part.split(',').map(|n| n.trim().parse().ok()).collect()
+            } else {
+                part.chars().map(|c| c.to_digit(10).map(|n| n as usize)).collect()
+            };
+            counts.filter(|counts| counts.iter().all(|&n| n < 128)).ok_or_else(expected)
+        };
+        Ok(Rule::new(&counts(birth, 'B')?, &counts(survival, 'S')?))
+    }
+}
+
+/// A Life-like automaton: cells that live or die each generation by how many of their neighbours are alive.
+pub trait Automaton {
+    /// Moves on a generation, saying whether anything changed.
+    fn step(&mut self) -> bool;
+
+    /// How many cells are alive.
+    fn population(&self) -> usize;
+}
+
+/// An automaton keeping every cell of a finite topology in an array, for when much of it is alive, or when cells can
+/// come to life with no live neighbours at all.
+pub struct Dense<T: Finite> {
+    topology: T,
+    rule: Rule,
+    alive: Vec<bool>,
+}
+
+impl<T: Finite> Dense<T> {
+    /// `alive` has a state for each of the topology's cells.
+    pub fn new(topology: T, rule: Rule, alive: Vec<bool>) -> Self {
+        assert_eq!(topology.len(), alive.len(), "A state is needed for every cell");
+        Dense { topology, rule, alive }
+    }
+
+    pub fn alive(&self) -> &[bool] {
+        &self.alive
+    }
+}
+
+impl<T: Finite> Automaton for Dense<T> {
+    fn step(&mut self) -> bool {
+        let mut flips = vec![];
+        for &cell in self.topology.cells() {
+            let neighbours = self.topology.neighbours(cell).filter(|&n| self.alive[n]).count();
+            if self.rule.next(self.alive[cell], neighbours) != self.alive[cell] {
+                flips.push(cell);
+            }
+        }
+        for &cell in &flips {
+            self.alive[cell] = !self.alive[cell];
+        }
+        !flips.is_empty()
+    }
+
+    fn population(&self) -> usize {
+        self.alive.iter().filter(|&&alive| alive).count()
+    }
+}
+
+/// An automaton keeping just the live cells, so it can spread across an unbounded topology. Only cells next to a live
+/// one are looked at, so nothing can come to life with no live neighbours.
+pub struct Sparse<T: Topology> {
+    topology: T,
+    rule: Rule,
+    alive: HashSet<T::Cell>,
+}
+
+impl<T: Topology> Sparse<T> {
+    pub fn new(topology: T, rule: Rule, alive: HashSet<T::Cell>) -> Self {
+        assert!(!rule.born(0), "Cells born from nothing need a finite topology");
+        Sparse { topology, rule, alive }
+    }
+
+    pub fn alive(&self) -> &HashSet<T::Cell> {
+        &self.alive
+    }
+}
+
+impl<T: Topology> Automaton for Sparse<T> {
+    fn step(&mut self) -> bool {
+        let mut counts: HashMap<T::Cell, usize> = HashMap::with_capacity(self.alive.len() * 4);
+        for &cell in &self.alive {
+            // Live cells count even with no live neighbours, in case they survive that.
+            counts.entry(cell).or_insert(0);
+            for neighbour in self.topology.neighbours(cell) {
+                *counts.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+        let next: HashSet<T::Cell> = counts
+            .into_iter()
+            .filter(|(cell, n)| self.rule.next(self.alive.contains(cell), *n))
+            .map(|(cell, _)| cell)
+            .collect();
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    fn population(&self) -> usize {
+        self.alive.len()
+    }
+}
+
+/// The shapes of world a pattern can be run in from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// The square grid, eight neighbours each.
+    Square,
+    /// The square grid, four neighbours each.
+    Square4,
+    /// The hexagons of Day 24, six neighbours each.
+    Hex,
+    /// Day 17's pocket dimension, 26 neighbours each.
+    Cube,
+    /// Day 17's pocket dimension with another dimension again, 80 neighbours each.
+    Tesseract,
+}
+
+impl Shape {
+    pub const NAMES: &'static str = "square, square4, hex, 3d or 4d";
+
+    pub fn from_name(name: &str) -> Option<Shape> {
+        match name {
+            "square" => Some(Shape::Square),
+            "square4" => Some(Shape::Square4),
+            "hex" => Some(Shape::Hex),
+            "3d" => Some(Shape::Cube),
+            "4d" => Some(Shape::Tesseract),
+            _ => None,
+        }
+    }
+}
+
+/// Runs the live (`#`) cells of a map for `generations`, laid out in the first two dimensions of `shape`, giving the
+/// population after each one. On the square grids `draw` gets to see the live cells each generation too.
+pub fn run_pattern(
+    start: &Grid2D<bool>,
+    shape: Shape,
+    rule: Rule,
+    generations: usize,
+    mut draw: impl FnMut(usize, &HashSet<(i64, i64)>),
+) -> Vec<usize> {
+    let live: Vec<(i64, i64)> = start
+        .iter()
+        .filter(|(_, &alive)| alive)
+        .map(|((row, col), _)| (i64::try_from(row).unwrap(), i64::try_from(col).unwrap()))
+        .collect();
+    match shape {
+        Shape::Square | Shape::Square4 => {
+            let mut life = Sparse::new(Square { diagonals: shape == Shape::Square }, rule, live.into_iter().collect());
+            populations(&mut life, generations, |generation, life| draw(generation, life.alive()))
+        }
+        Shape::Hex => {
+            // Rows go north-west, so the map's shape is kept, only sheared.
+            let tiles = live.into_iter().map(|(row, col)| (col - row, -row)).collect();
+            populations(&mut Sparse::new(Hex, rule, tiles), generations, |_, _| {})
+        }
+        Shape::Cube => {
+            let cubes = live.into_iter().map(|(row, col)| [row, col, 0]).collect();
+            populations(&mut Sparse::new(Lattice::<3>::new(), rule, cubes), generations, |_, _| {})
+        }
+        Shape::Tesseract => {
+            let cubes = live.into_iter().map(|(row, col)| [row, col, 0, 0]).collect();
+            populations(&mut Sparse::new(Lattice::<4>::new(), rule, cubes), generations, |_, _| {})
+        }
+    }
+}
+
+/// Runs `life` for `generations`, giving the population at the start and after each one, and showing each generation to
+/// `seen`. It can be stopped between generations by the time budget.
+pub fn populations<A: Automaton>(life: &mut A, generations: usize, mut seen: impl FnMut(usize, &A)) -> Vec<usize> {
+    let mut populations = vec![life.population()];
+    seen(0, life);
+    for generation in 1..=generations {
+        crate::cancel::check();
+        life.step();
+        populations.push(life.population());
+        seen(generation, life);
+    }
+    populations
+}
+
+/// The live cells of a square grid, cut down to just the part with any in.
+pub fn draw_square(alive: &HashSet<(i64, i64)>) -> String {
+    let rows = alive.iter().map(|&(row, _)| row);
+    let cols = alive.iter().map(|&(_, col)| col);
+    let (Some(top), Some(bottom)) = (rows.clone().min(), rows.max()) else {
+        return String::new();
+    };
+    let (left, right) = (cols.clone().min().unwrap(), cols.max().unwrap());
+    let mut drawn = String::new();
+    for row in top..=bottom {
+        drawn.extend((left..=right).map(|col| if alive.contains(&(row, col)) { '#' } else { '.' }));
+        drawn.push('\n');
+    }
+    drawn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule() {
+        assert_eq!(Ok(Rule::CONWAY), "B3/S23".parse());
+        assert_eq!(Ok(Rule::new(&[2], &[1, 2])), "b2/s12".parse());
+        assert_eq!(Ok(Rule::new(&[3], &[2, 3, 10])), "B3/S2,3,10".parse());
+        assert_eq!(Ok(Rule::new(&[0], &[])), "B0/S".parse());
+        assert!("B3S23".parse::<Rule>().is_err());
+        assert!("B3/X23".parse::<Rule>().is_err());
+        assert!("B3/S2,x".parse::<Rule>().is_err());
+
+        let rule = Rule::CONWAY;
+        let next = (rule.next(false, 3), rule.next(false, 2), rule.next(true, 2), rule.next(true, 4));
+        assert_eq!((true, false, true, false), next);
+    }
+
+    #[test]
+    fn test_glider() {
+        let glider = Grid2D::parse(99, ".#.\n..#\n###\n").unwrap();
+        let mut last = String::new();
+        let populations = run_pattern(&glider, Shape::Square, Rule::CONWAY, 4, |_, alive| last = draw_square(alive));
+        assert_eq!(vec![5; 5], populations);
+        // Four generations on, it's the same shape one down and one across.
+        assert_eq!(glider.to_string(), last);
+    }
+
+    #[test]
+    fn test_shapes() {
+        // Day 17's example.
+        let start = Grid2D::parse(17, ".#.\n..#\n###\n").unwrap();
+        assert_eq!(Some(&112), run_pattern(&start, Shape::Cube, Rule::CONWAY, 6, |_, _| {}).last());
+        assert_eq!(Lattice::<4>::new().neighbours([0; 4]).count(), 80);
+        // A blinker on the four-neighbour grid dies out.
+        let blinker = Grid2D::parse(99, "###\n").unwrap();
+        assert_eq!(vec![3, 1, 0], run_pattern(&blinker, Shape::Square4, Rule::CONWAY, 2, |_, _| {}));
+    }
+
+    #[test]
+    fn test_dense() {
+        // A cell lights up with no neighbours lit, which a sparse automaton couldn't do, then they all stay lit.
+        let grid = Grid2D::parse(99, "..\n..\n").unwrap();
+        let topology = Fixed::from_grid(&grid, |_: &bool| true, |row, col| grid.neighbours8(row, col));
+        let mut life = Dense::new(topology, "B0/S0123".parse().unwrap(), vec![false; 4]);
+        assert!(life.step());
+        assert_eq!(4, life.population());
+        assert!(!life.step());
+    }
+}
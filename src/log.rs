@@ -0,0 +1,129 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much a day's commentary matters. Lower levels are shown more readily.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Something looks wrong with the input or the working, just before a day gives up on it.
+    Warn = 1,
+    /// A summary of how a day got its answer, e.g. how many rules it read.
+    Debug = 2,
+    /// Step by step progress, e.g. each round of a simulation.
+    Trace = 3,
+}
+
+/// The most detailed level being shown, or 0 for nothing at all.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Shows commentary up to and including `level`, or none at all for `None`. Warnings are shown until told otherwise.
+pub fn set_max_level(level: Option<Level>) {
+    MAX_LEVEL.store(level.map_or(0, |l| l as u8), Ordering::Relaxed);
+}
+
+/// Whether commentary at `level` is being shown, for days that have to do some work to put it together.
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// Writes a line of a day's commentary, either to stderr or to the capture buffer if one is active on this thread.
+///
+/// Days call this through the `warn!`, `debug!` and `trace!` macros rather than directly, which skip formatting the
+/// message altogether when its level isn't shown.
+pub fn write(level: Level, args: fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+    let printed = CAPTURED.with(|c| match c.borrow_mut().as_mut() {
+        Some(buf) => {
+            fmt::write(buf, args).unwrap();
+            buf.push('\n');
+            true
+        }
+        None => false,
+    });
+    if !printed {
+        eprintln!("{}", args);
+    }
+}
+
+/// Runs `f`, collecting any commentary it writes on this thread instead of printing it.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = CAPTURED.with(|c| c.replace(Some(String::new())));
+    let result = f();
+    let captured = CAPTURED.with(|c| c.replace(previous)).unwrap_or_default();
+    (result, captured)
+}
+
+/// Writes a line of commentary at the given level, if that level is being shown.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)*))
+        }
+    };
+}
+
+/// Like `eprintln!`, for something that looks wrong. Shown unless running with `-q`.
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::log!($crate::log::Level::Warn, $($arg)*)
+    };
+}
+
+/// Like `eprintln!`, for a summary of how a day got its answer. Shown with `-v`.
+#[macro_export]
+macro_rules! debug {
+    () => {
+        $crate::log!($crate::log::Level::Debug, "")
+    };
+    ($($arg:tt)*) => {
+        $crate::log!($crate::log::Level::Debug, $($arg)*)
+    };
+}
+
+/// Like `eprintln!`, for step by step progress. Shown with `-vv`.
+#[macro_export]
+macro_rules! trace {
+    () => {
+        $crate::log!($crate::log::Level::Trace, "")
+    };
+    ($($arg:tt)*) => {
+        $crate::log!($crate::log::Level::Trace, $($arg)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture() {
+        // The level is global, so everything that changes it is in this one test.
+        set_max_level(Some(Level::Debug));
+        let (n, text) = capture(|| {
+            debug!("one {}", 1);
+            trace!("hidden");
+            let (_, inner) = capture(|| warn!("inner"));
+            assert_eq!("inner\n", inner);
+            debug!();
+            debug!("two");
+            2
+        });
+        assert_eq!(2, n);
+        assert_eq!("one 1\n\ntwo\n", text);
+
+        set_max_level(None);
+        assert_eq!(((), String::new()), capture(|| warn!("quiet")));
+        assert!(!enabled(Level::Warn));
+
+        set_max_level(Some(Level::Warn));
+        assert!(enabled(Level::Warn) && !enabled(Level::Debug));
+    }
+}
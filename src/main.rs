@@ -1,103 +1,833 @@
 #![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::uninlined_format_args)]
+use std::cell::Cell;
 use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
 use std::process;
+use std::time::{Duration, Instant};
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
+use advent2020::cancel::panic_message;
+use advent2020::checkpoint::{self, Snapshot};
+use advent2020::input::normalise;
+use advent2020::life;
+use advent2020::params;
+use advent2020::progress;
+use advent2020::{
+    capture, default_jobs, fmt_bright, fmt_green, fmt_micros, fmt_red, git_revision, input_hash, map_parallel, print_day,
+    print_duration, render_report, BenchRecord, Cancel, Check, Command, CompareRecord, Config, Entry, ErrorRecord, Format,
+    Grid2D, History, Manifest, ParseError, Puzzle, Record, Registry, ReportEntry, Rng, Solution, Source, Stats, Timings,
+    VerifyRecord,
+};
 
 fn main() {
-    match day_arg(env::args()) {
-        Some(1) => day1::run(),
-        Some(2) => day2::run(),
-        Some(3) => day3::run(),
-        Some(4) => day4::run(),
-        Some(5) => day5::run(),
-        Some(6) => day6::run(),
-        Some(7) => day7::run(),
-        Some(8) => day8::run(),
-        Some(9) => day9::run(),
-        Some(10) => day10::run(),
-        Some(11) => day11::run(),
-        Some(12) => day12::run(),
-        Some(13) => day13::run(),
-        Some(14) => day14::run(),
-        Some(15) => day15::run(),
-        Some(16) => day16::run(),
-        Some(17) => day17::run(),
-        Some(18) => day18::run(),
-        Some(19) => day19::run(),
-        Some(20) => day20::run(),
-        Some(21) => day21::run(),
-        Some(22) => day22::run(),
-        Some(23) => day23::run(),
-        Some(24) => day24::run(),
-        Some(25) => day25::run(),
-        Some(0) => {
-            day1::run();
-            day2::run();
-            day3::run();
-            day4::run();
-            day5::run();
-            day6::run();
-            day7::run();
-            day8::run();
-            day9::run();
-            day10::run();
-            day11::run();
-            day12::run();
-            day13::run();
-            day14::run();
-            day15::run();
-            day16::run();
-            day17::run();
-            day18::run();
-            day19::run();
-            day20::run();
-            day21::run();
-            day22::run();
-            day23::run();
-            day24::run();
-            day25::run();
-        }
-        Some(x) => { eprintln!("Unimplemented day {}", x); process::exit(1);},
-        None => { eprintln!("No valid day on command line {:#?}", env::args()); process::exit(1);},
-    }
-}
-
-fn day_arg(mut args: env::Args) -> Option<i32> {
-    args.next();
-
-    let day = match args.next() {
-        Some(day) => day.parse::<i32>(),
-        None => return None,
-    };
-
-    if let Ok(i) = day {
-        return Some(i);
-    }
-
-    None
+    let config = Config::new(env::args()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        print_usage();
+        process::exit(1);
+    });
+    advent2020::log::set_max_level(config.log_level);
+    quiet_isolated_panics();
+    progress::set_mode(progress_mode(&config));
+    if let Err(err) = config.load_params().map(params::set).and_then(|()| set_checkpoint(&config)) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+
+    let registry = advent2020::days::registry();
+
+    match config.command {
+        Command::List => {
+            for puzzle in registry.iter() {
+                println!("{:>2} {}", puzzle.id(), puzzle.title());
+            }
+        }
+        Command::Run(0) => {
+            if !run_every(&registry, &config) {
+                process::exit(1);
+            }
+        }
+        Command::Run(x) => {
+            let puzzle = find_day(&registry, x);
+            let ok = if config.inputs.is_some() { batch(puzzle, &config) } else { run(puzzle, &config) };
+            if checkpoint::resume_pending() {
+                eprintln!("The snapshot wasn't of this input or these parameters, so the day started from the beginning");
+            }
+            if !ok {
+                process::exit(1);
+            }
+        }
+        Command::All => {
+            if !run_all(&registry, &config) {
+                process::exit(1);
+            }
+        }
+        Command::Bench(0) => {
+            let revision = git_revision();
+            let failed = registry.iter().filter(|&puzzle| !bench(puzzle, &config, &revision)).count();
+            if failed > 0 {
+                process::exit(1);
+            }
+        }
+        Command::Bench(x) => {
+            let puzzle = find_day(&registry, x);
+            if !bench(puzzle, &config, &git_revision()) {
+                process::exit(1);
+            }
+        }
+        Command::Verify(0) => {
+            if !verify(&registry.iter().collect::<Vec<_>>(), &config) {
+                process::exit(1);
+            }
+        }
+        Command::Verify(x) => {
+            let puzzle = find_day(&registry, x);
+            if !verify(&[puzzle], &config) {
+                process::exit(1);
+            }
+        }
+        Command::Tui => {
+            if let Err(err) = advent2020::tui::run(&registry, &config) {
+                eprintln!("Could not run the dashboard: {}", err);
+                process::exit(1);
+            }
+        }
+        Command::Compare => {
+            if !compare(&config) {
+                process::exit(1);
+            }
+        }
+        Command::Generate(x) => {
+            let puzzle = find_day(&registry, x);
+            if !generate(puzzle, &config) {
+                process::exit(1);
+            }
+        }
+        Command::New(x) => {
+            if !new_day(&registry, x, &config) {
+                process::exit(1);
+            }
+        }
+        Command::Report(x) => {
+            if !report(&registry, x, &config) {
+                process::exit(1);
+            }
+        }
+        Command::Life => {
+            if !life(&config) {
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Long days show their progress when run one at a time, but not side by side or in the dashboard, where it would only
+/// get in the way, nor when asked to be quiet.
+fn progress_mode(config: &Config) -> progress::Mode {
+    match config.command {
+        Command::Run(_) | Command::Bench(_) | Command::Verify(_) if config.log_level.is_some() => {
+            progress::Mode::for_stderr()
+        }
+        _ => progress::Mode::Off,
+    }
+}
+
+/// Has the long loops save snapshots where asked, and loads the snapshot to carry on from, checking it's of the day
+/// being run.
+fn set_checkpoint(config: &Config) -> Result<(), String> {
+    let resume = config.resume.as_deref().map(Snapshot::load).transpose()?;
+    if let (Some(snapshot), Some(path)) = (&resume, &config.resume) {
+        if config.command != Command::Run(snapshot.day) {
+            return Err(format!("{} is a snapshot of day {}", path.display(), snapshot.day));
+        }
+    }
+    checkpoint::set(config.checkpoint_path().map(Path::to_path_buf), resume);
+    Ok(())
+}
+
+/// The day asked for, or exits saying which days there are.
+fn find_day(registry: &Registry, day: u32) -> &dyn Puzzle {
+    registry.get(day).unwrap_or_else(|| {
+        eprintln!("Unimplemented day {} (known days: {:?})", day, registry.ids());
+        process::exit(1);
+    })
+}
+
+/// How to call the binary, for when the command line can't be read.
+fn print_usage() {
+    eprintln!("Usage: advent2020 <day|0|list> [input file|-] [--part 1|2] [--data-dir <dir>] [--format text|json] [-v|-vv|-q]");
+    eprintln!("       advent2020 <day> --inputs <dir> [--part 1|2] [--timeout [<day>=]<secs>]... [--format text|json]");
+    eprintln!("                         [--params <file>] [--param <day>.<name>=<value>]...");
+    eprintln!("       advent2020 <day> [input file|-] [--checkpoint <file>] [--resume <file>]");
+    eprintln!("       advent2020 all [--jobs <n>] [--timeout [<day>=]<secs>]... [--data-dir <dir>] [--format text|json]");
+    eprintln!("       advent2020 bench <day|0> [input file|-] [--runs <n>] [--warmup <n>]");
+    eprintln!("       advent2020 verify <day|0> [input file|-] [--answers <file>]");
+    eprintln!("       advent2020 tui [--data-dir <dir>]");
+    eprintln!("       advent2020 compare [--baseline <revision>] [--threshold <percent>] [--history <file>]");
+    eprintln!("       advent2020 generate <day> [output file|-] [--seed <n>] [--scale <n>]");
+    eprintln!("       advent2020 new <day> [--title <title>]");
+    eprintln!("       advent2020 report <day|0> [output file|-] [--visuals] [--part 1|2] [--data-dir <dir>]");
+    eprintln!("       advent2020 life <pattern file|-> [--rule B3/S23] [--topology square|square4|hex|3d|4d] [--generations <n>]");
+}
+
+/// Reads a day's input, reporting the problem if it can't be read.
+fn read_input(puzzle: &dyn Puzzle, config: &Config) -> Option<String> {
+    match config.read_input(puzzle.id()) {
+        Ok(data) => Some(data),
+        Err(err) => {
+            report_failure(puzzle, &format!("Could not read {}: {}", config.input_name(puzzle.id()), err), config);
+            None
+        }
+    }
+}
+
+/// Reports why a day couldn't give an answer, as an `ErrorRecord` in place of its records in JSON.
+fn report_failure(puzzle: &dyn Puzzle, msg: &str, config: &Config) {
+    match config.format {
+        Format::Text => {
+            println!("{}", fmt_red(&msg));
+            println!();
+        }
+        Format::Json => print_error_record(puzzle.id(), msg, config.input_path(puzzle.id()).as_deref()),
+    }
+}
+
+fn print_error_record(day: u32, error: &str, input: Option<&Path>) {
+    println!("{}", ErrorRecord { day, error, input }.to_json());
+}
+
+thread_local! {
+    /// Whether this thread is in the middle of an `isolate`, which reports any panic itself.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps the usual panic message and backtrace note off stderr for panics `isolate` catches, since they'd land in the
+/// middle of other days' output or the summary table. Any other panic is printed as normal.
+fn quiet_isolated_panics() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !ISOLATED.with(Cell::get) {
+            default_hook(info);
+        }
+    }));
+}
+
+/// Runs some of a day's work within the time budget, catching any panic, so that one day going wrong can't take the
+/// others down with it. Gives why it stopped early otherwise, e.g. `timed out after 2.0s` or `panicked: Invalid angle.`
+fn isolate<T>(timeout: Option<Duration>, f: impl FnOnce() -> T) -> Result<T, String> {
+    let was_isolated = ISOLATED.with(|isolated| isolated.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| Cancel::new(timeout).run(f)));
+    ISOLATED.with(|isolated| isolated.set(was_isolated));
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(stopped)) => Err(stopped.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(&*payload))),
+    }
+}
+
+/// Runs some of a day's work in isolation, reporting why if it couldn't parse its input, didn't finish or panicked.
+fn attempt<T>(puzzle: &dyn Puzzle, config: &Config, f: impl FnOnce() -> Result<T, ParseError>) -> Option<T> {
+    match isolate(config.timeout_for(puzzle.id()), f) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(err)) => {
+            match config.format {
+                // The rendered error already says which day it is.
+                Format::Text => {
+                    println!("{}", fmt_red(&err.render()));
+                    println!();
+                }
+                Format::Json => report_failure(puzzle, &err.to_string(), config),
+            }
+            None
+        }
+        Err(msg) => {
+            report_failure(puzzle, &format!("Stopped: {}", msg), config);
+            None
+        }
+    }
+}
+
+/// Runs one day against its configured input, returning false if the input couldn't be read or parsed.
+fn run(puzzle: &dyn Puzzle, config: &Config) -> bool {
+    if config.format == Format::Text {
+        print_day(puzzle.id(), puzzle.title());
+    }
+
+    let Some(data) = read_input(puzzle, config) else {
+        return false;
+    };
+
+    let Some((solution, timings)) = attempt(puzzle, config, || puzzle.solve_timed(&data, config.parts)) else {
+        return false;
+    };
+
+    match config.format {
+        Format::Text => {
+            for (part, answer) in solution.answers() {
+                println!("Part {}: {}", part, fmt_bright(answer));
+            }
+            print_duration(timings.total());
+        }
+        Format::Json => print_records(puzzle.id(), &solution, timings, config.input_path(puzzle.id()).as_deref()),
+    }
+    true
+}
+
+/// Runs one day against every input in the `--inputs` directory, then prints a table of how each went.
+///
+/// An input the day can't read, parse, finish or even survive is reported in its own row, and the rest still run.
+/// Returns false if any of them failed.
+fn batch(puzzle: &dyn Puzzle, config: &Config) -> bool {
+    let dir = config.inputs.as_deref().unwrap_or_else(|| Path::new("."));
+    let files = match config.batch_inputs(puzzle.id()) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => {
+            eprintln!("No inputs in {}", dir.display());
+            return false;
+        }
+        Err(err) => {
+            eprintln!("Could not read {}: {}", dir.display(), err);
+            return false;
+        }
+    };
+    if config.format == Format::Text {
+        print_day(puzzle.id(), puzzle.title());
+    }
+
+    let results: Vec<Result<(Solution, Timings), String>> = files
+        .iter()
+        .map(|path| match fs::read_to_string(path) {
+            Ok(data) => solve_isolated(puzzle, &data, config),
+            Err(err) => Err(format!("Could not read: {}", err)),
+        })
+        .collect();
+
+    match config.format {
+        Format::Text => {
+            let rows: Vec<(Vec<String>, bool)> = files
+                .iter()
+                .zip(&results)
+                .map(|(path, result)| {
+                    let file = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    let row = match result {
+                        Ok((solution, timings)) => vec![
+                            file,
+                            solution.part1.as_ref().map(ToString::to_string).unwrap_or_default(),
+                            solution.part2.as_ref().map(ToString::to_string).unwrap_or_default(),
+                            fmt_micros(timings.total()),
+                            String::from("ok"),
+                        ],
+                        Err(msg) => vec![file, String::new(), String::new(), String::new(), msg.clone()],
+                    };
+                    (row, result.is_ok())
+                })
+                .collect();
+            print_table(&["Input", "Part 1", "Part 2", "Time", "Status"], &rows, &[0]);
+        }
+        Format::Json => {
+            for (path, result) in files.iter().zip(&results) {
+                match result {
+                    Ok((solution, timings)) => print_records(puzzle.id(), solution, *timings, Some(path)),
+                    Err(msg) => print_error_record(puzzle.id(), msg, Some(path)),
+                }
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    if config.format == Format::Text {
+        println!("{} inputs, {} failed.", results.len(), failed);
+    }
+    failed == 0
+}
+
+/// Solves a day in isolation, turning a parse error, running out of time or a panic into a message.
+fn solve_isolated(puzzle: &dyn Puzzle, data: &str, config: &Config) -> Result<(Solution, Timings), String> {
+    isolate(config.timeout_for(puzzle.id()), || puzzle.solve_timed(data, config.parts)).and_then(|solved| solved.map_err(|err| err.to_string()))
+}
+
+/// Runs every day one after another, carrying on past any that fail, then says how many did. Returns false if any
+/// failed.
+fn run_every(registry: &Registry, config: &Config) -> bool {
+    let failed: Vec<u32> = registry.iter().filter(|&puzzle| !run(puzzle, config)).map(Puzzle::id).collect();
+    if config.format == Format::Text {
+        if failed.is_empty() {
+            println!("{} days, none failed.", registry.len());
+        } else {
+            let days: Vec<String> = failed.iter().map(ToString::to_string).collect();
+            println!("{} days, {} failed: {}", registry.len(), failed.len(), fmt_red(&days.join(", ")));
+        }
+    }
+    failed.is_empty()
+}
+
+/// Prints the JSON records for each part of a day that was run on `input`.
+fn print_records(day: u32, solution: &Solution, timings: Timings, input: Option<&Path>) {
+    // Parsing is shared by both parts, so it's counted against the first one run and the records add up to the whole
+    // day.
+    let mut parse = Some(timings.parse);
+    for (part, answer) in solution.answers() {
+        let duration = if part == 1 { timings.part1 } else { timings.part2 };
+        let record = Record {
+            day,
+            part,
+            answer,
+            duration: duration + parse.take().unwrap_or_default(),
+            input,
+        };
+        println!("{}", record.to_json());
+    }
+}
+
+/// How one day went when running them all together.
+struct Outcome<'a> {
+    puzzle: &'a dyn Puzzle,
+    result: Result<(Solution, Timings), String>,
+    /// Anything the day had to say while solving.
+    notes: String,
+}
+
+/// Runs every day on a pool of workers, then prints their commentary and a summary table in day order.
+///
+/// Returns false if any day failed.
+fn run_all(registry: &Registry, config: &Config) -> bool {
+    let puzzles: Vec<&dyn Puzzle> = registry.iter().collect();
+    let jobs = config.jobs.unwrap_or_else(default_jobs);
+
+    let start = Instant::now();
+    let outcomes = map_parallel(&puzzles, jobs, |&puzzle| {
+        let (result, notes) = capture(|| match config.read_input(puzzle.id()) {
+            Ok(data) => solve_isolated(puzzle, &data, config),
+            Err(err) => Err(format!("Could not read {}: {}", config.input_name(puzzle.id()), err)),
+        });
+        Outcome { puzzle, result, notes }
+    });
+    let wall_clock = start.elapsed();
+
+    match config.format {
+        Format::Text => print_summary(&outcomes),
+        Format::Json => {
+            for outcome in &outcomes {
+                let input = config.input_path(outcome.puzzle.id());
+                match &outcome.result {
+                    Ok((solution, timings)) => print_records(outcome.puzzle.id(), solution, *timings, input.as_deref()),
+                    Err(msg) => print_error_record(outcome.puzzle.id(), msg, input.as_deref()),
+                }
+            }
+        }
+    }
+
+    let failed = outcomes.iter().filter(|o| o.result.is_err()).count();
+    if config.format == Format::Text {
+        let solving: Duration = outcomes
+            .iter()
+            .filter_map(|o| o.result.as_ref().ok())
+            .map(|(_, timings)| timings.total())
+            .sum();
+        println!(
+            "{} days, {} failed. {} wall clock, {} solving, {} workers.",
+            outcomes.len(),
+            failed,
+            fmt_micros(wall_clock),
+            fmt_micros(solving),
+            jobs.min(outcomes.len())
+        );
+    }
+    failed == 0
+}
+
+fn print_summary(outcomes: &[Outcome]) {
+    for outcome in outcomes.iter().filter(|o| !o.notes.is_empty()) {
+        print_day(outcome.puzzle.id(), outcome.puzzle.title());
+        print!("{}", outcome.notes);
+        println!();
+    }
+
+    let rows: Vec<Vec<String>> = outcomes
+        .iter()
+        .map(|o| {
+            let (part1, part2, time, status) = match &o.result {
+                Ok((solution, timings)) => (
+                    solution.part1.as_ref().map(ToString::to_string).unwrap_or_default(),
+                    solution.part2.as_ref().map(ToString::to_string).unwrap_or_default(),
+                    fmt_micros(timings.total()),
+                    String::from("ok"),
+                ),
+                Err(msg) => (String::new(), String::new(), String::new(), msg.clone()),
+            };
+            vec![o.puzzle.id().to_string(), o.puzzle.title().to_string(), part1, part2, time, status]
+        })
+        .collect();
+
+    let rows: Vec<(Vec<String>, bool)> = rows.into_iter().zip(outcomes).map(|(row, o)| (row, o.result.is_ok())).collect();
+    print_table(&["Day", "Title", "Part 1", "Part 2", "Time", "Status"], &rows, &[1]);
+}
+
+/// Prints `rows` under `header` with the columns lined up, and the last column as a status coloured by whether its row
+/// went ok. Columns are right-aligned, apart from those in `left`.
+fn print_table(header: &[&str], rows: &[(Vec<String>, bool)], left: &[usize]) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for (row, _) in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    // Padding is worked out before colouring, as the escape codes would otherwise count towards the width.
+    let status = header.len() - 1;
+    let line = |row: &[String]| -> String {
+        row[..status]
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if left.contains(&i) {
+                    format!("{:<w$}  ", cell, w = widths[i])
+                } else {
+                    format!("{:>w$}  ", cell, w = widths[i])
+                }
+            })
+            .collect()
+    };
+    println!("{}{}", line(&header.iter().map(ToString::to_string).collect::<Vec<_>>()), header[status]);
+    for (row, ok) in rows {
+        let coloured = if *ok { fmt_green(&row[status]) } else { fmt_red(&row[status]) };
+        println!("{}{}", line(row), coloured);
+    }
+    println!();
+}
+
+/// Benchmarks each phase of one day and adds the results to the history, returning false if the input couldn't be read
+/// or parsed.
+fn bench(puzzle: &dyn Puzzle, config: &Config, revision: &str) -> bool {
+    if config.format == Format::Text {
+        print_day(puzzle.id(), puzzle.title());
+    }
+
+    let Some(data) = read_input(puzzle, config) else {
+        return false;
+    };
+
+    // Everything is timed up front, so none of the printing below lands in a timed region.
+    let Some(samples) = attempt(puzzle, config, || puzzle.bench(&data, config.warmup, config.runs, config.parts)) else {
+        return false;
+    };
+    let phases = [("parse", &samples.parse), ("part1", &samples.part1), ("part2", &samples.part2)];
+    let path = config.input_path(puzzle.id());
+    let fingerprint = params::fingerprint(puzzle.id());
+
+    if config.format == Format::Text {
+        println!(
+            "{:<6} {:>5} {:>10} {:>10} {:>10} {:>10}",
+            "phase", "runs", "min", "median", "mean", "p95"
+        );
+    }
+    let mut entries = vec![];
+    for (phase, timings) in &phases {
+        let Some(stats) = Stats::from_samples(timings) else {
+            continue;
+        };
+        entries.push(Entry::new(revision, puzzle.id(), phase, stats, fingerprint, path.as_deref()));
+        match config.format {
+            Format::Text => println!(
+                "{:<6} {:>5} {:>10} {:>10} {:>10} {:>10}",
+                phase,
+                stats.runs,
+                fmt_micros(stats.min),
+                fmt_micros(stats.median),
+                fmt_micros(stats.mean),
+                fmt_micros(stats.p95)
+            ),
+            Format::Json => {
+                let record = BenchRecord {
+                    day: puzzle.id(),
+                    phase,
+                    stats,
+                    input: path.as_deref(),
+                };
+                println!("{}", record.to_json());
+            }
+        }
+    }
+    if config.format == Format::Text {
+        println!();
+    }
+
+    if let Err(err) = History::append(&config.history, &entries) {
+        eprintln!("Could not add to {}: {}", config.history.display(), err);
+    }
+    true
+}
+
+/// Compares the latest benchmarks against a baseline, returning false if anything got slower than the threshold.
+fn compare(config: &Config) -> bool {
+    let history = match History::load(&config.history) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+    let Some(current) = history.latest_revision() else {
+        eprintln!("Nothing in {} yet, run bench first", config.history.display());
+        return false;
+    };
+    let baseline = match &config.baseline {
+        Some(revision) => history.find_revision(revision),
+        None => history.previous_revision(current),
+    };
+    let Some(baseline) = baseline else {
+        match &config.baseline {
+            Some(revision) => eprintln!("No single revision {} in {}", revision, config.history.display()),
+            None => eprintln!("Only {} is in {}, so there's nothing to compare it with", current, config.history.display()),
+        }
+        return false;
+    };
+
+    let comparisons = history.compare(baseline, current);
+    if config.format == Format::Text {
+        println!("Comparing {} against {}:", current, baseline);
+        println!(
+            "{:>3}  {:<6} {:>10} {:>10} {:>8}  ",
+            "Day", "phase", "baseline", "current", "change"
+        );
+    }
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let regressed = comparison.regressed(config.threshold);
+        if regressed {
+            regressions += 1;
+        }
+        match config.format {
+            Format::Text => println!(
+                "{:>3}  {:<6} {:>10} {:>10} {:>+7.1}%  {}",
+                comparison.day,
+                comparison.phase,
+                fmt_micros(comparison.baseline),
+                fmt_micros(comparison.current),
+                comparison.change_pct(),
+                if regressed { fmt_red(&"slower") } else { fmt_green(&"ok") }
+            ),
+            Format::Json => println!("{}", CompareRecord { comparison, regressed }.to_json()),
+        }
+    }
+    if config.format == Format::Text {
+        println!();
+        println!(
+            "{} compared, {} more than {}% slower.",
+            comparisons.len(),
+            regressions,
+            config.threshold
+        );
+    }
+    regressions == 0
+}
+
+/// Checks days against the answers manifest, returning false if any answer was wrong or a day couldn't be run.
+fn verify(puzzles: &[&dyn Puzzle], config: &Config) -> bool {
+    let path = config.answers_path();
+    let manifest = match Manifest::load(&path) {
+        Ok(manifest) => manifest,
+        // Without the default manifest we can still run everything, it's just all unknown.
+        Err(err) if config.answers.is_none() && !path.exists() => {
+            eprintln!("{}, so no answers are known", err);
+            Manifest::default()
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    let mut counts = [0; 3];
+    for &puzzle in puzzles {
+        if config.format == Format::Text {
+            print_day(puzzle.id(), puzzle.title());
+        }
+        let Some(data) = read_input(puzzle, config) else {
+            ok = false;
+            continue;
+        };
+
+        let Some((solution, _)) = attempt(puzzle, config, || puzzle.solve_timed(&data, config.parts)) else {
+            ok = false;
+            continue;
+        };
+        let input = config.input_path(puzzle.id());
+        for (part, answer) in solution.answers() {
+            let expected = input.as_deref().and_then(|path| manifest.expected(path, part));
+            let check = Check::new(expected, answer);
+            match &check {
+                Check::Pass => counts[0] += 1,
+                Check::Fail { .. } => counts[1] += 1,
+                Check::Unknown => counts[2] += 1,
+            }
+
+            match config.format {
+                Format::Text => match &check {
+                    Check::Pass => println!("Part {}: {} {}", part, fmt_green(&"pass"), answer),
+                    Check::Unknown => println!("Part {}: unknown {}", part, answer),
+                    Check::Fail { expected, actual } => {
+                        println!("Part {}: {}", part, fmt_red(&"FAIL"));
+                        println!("    {}", fmt_red(&format!("- expected {}", expected)));
+                        println!("    {}", fmt_green(&format!("+ actual   {}", actual)));
+                    }
+                },
+                Format::Json => {
+                    let record = VerifyRecord {
+                        day: puzzle.id(),
+                        part,
+                        check: &check,
+                        input: input.as_deref(),
+                    };
+                    println!("{}", record.to_json());
+                }
+            }
+        }
+        if config.format == Format::Text {
+            println!();
+        }
+    }
+
+    if config.format == Format::Text {
+        println!("{} passed, {} failed, {} unknown.", counts[0], counts[1], counts[2]);
+    }
+    ok && counts[1] == 0
+}
+
+/// Makes up an input for one day and writes it out, returning false if the day has no generator or it couldn't be
+/// written.
+fn generate(puzzle: &dyn Puzzle, config: &Config) -> bool {
+    let seed = config.seed.unwrap_or_else(Rng::random_seed);
+    let Some(data) = puzzle.generate(&mut Rng::new(seed), config.scale) else {
+        eprintln!("Day {} has no generator", puzzle.id());
+        return false;
+    };
+    // The seed goes to stderr, so a failure found with it can be made again without spoiling the input on stdout.
+    eprintln!("Day {} generated with --seed {}", puzzle.id(), seed);
+
+    match &config.source {
+        Source::File(path) => {
+            if let Err(err) = fs::write(path, data) {
+                eprintln!("Could not write {}: {}", path.display(), err);
+                return false;
+            }
+        }
+        Source::Default | Source::Stdin => print!("{}", data),
+    }
+    true
+}
+
+/// Runs the pattern given as a Life-like automaton, drawing how it ends up on the square grids, and says how many cells
+/// are left alive.
+fn life(config: &Config) -> bool {
+    let name = config.input_name(0);
+    let data = match config.read_input(0) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", name, err);
+            return false;
+        }
+    };
+    let start = match Grid2D::parse(0, &normalise(&data)) {
+        Ok(start) => start,
+        Err(err) => {
+            eprintln!("{}", fmt_red(&err.render_in(&name)));
+            return false;
+        }
+    };
+
+    let mut last = String::new();
+    let populations = match isolate(config.timeout, || {
+        life::run_pattern(&start, config.shape, config.rule, config.generations, |generation, alive| {
+            if generation == config.generations {
+                last = life::draw_square(alive);
+            }
+        })
+    }) {
+        Ok(populations) => populations,
+        Err(err) => {
+            eprintln!("The pattern {}", err);
+            return false;
+        }
+    };
+    print!("{}", last);
+    println!("{} alive after {} generations", populations.last().unwrap_or(&0), config.generations);
+    true
+}
+
+/// Runs a day, or every day for 0, and writes a Markdown report of how they went. Returns false if the report couldn't
+/// be written or any day failed, though the failures are still in the report.
+fn report(registry: &Registry, day: u32, config: &Config) -> bool {
+    let puzzles: Vec<&dyn Puzzle> = match day {
+        0 => registry.iter().collect(),
+        x => vec![find_day(registry, x)],
+    };
+
+    // The days run side by side as for `all`, with their commentary kept out of the way.
+    let entries = map_parallel(&puzzles, config.jobs.unwrap_or_else(default_jobs), |&puzzle| {
+        let path = config.data_dir.join(format!("data{}.txt", puzzle.id()));
+        let (entry, _) = capture(|| match fs::read_to_string(&path) {
+            Ok(data) => {
+                let result = solve_isolated(puzzle, &data, config);
+                let visual = if config.visuals && result.is_ok() {
+                    isolate(config.timeout_for(puzzle.id()), || puzzle.visualise(&data)).ok().and_then(Result::ok).flatten()
+                } else {
+                    None
+                };
+                ReportEntry {
+                    day: puzzle.id(),
+                    title: puzzle.title().to_string(),
+                    result,
+                    // As the day read it, so a copy saved with CRLF line endings or a BOM is still the same input.
+                    input_hash: Some(input_hash(&normalise(&data))),
+                    visual,
+                }
+            }
+            Err(err) => ReportEntry {
+                day: puzzle.id(),
+                title: puzzle.title().to_string(),
+                result: Err(format!("Could not read {}: {}", path.display(), err)),
+                input_hash: None,
+                visual: None,
+            },
+        });
+        entry
+    });
+
+    let doc = render_report(&entries, &git_revision());
+    match &config.source {
+        Source::File(path) => {
+            if let Err(err) = fs::write(path, doc) {
+                eprintln!("Could not write {}: {}", path.display(), err);
+                return false;
+            }
+            eprintln!("Wrote the report on {} days to {}", entries.len(), path.display());
+        }
+        Source::Default | Source::Stdin => print!("{}", doc),
+    }
+    entries.iter().all(|e| e.result.is_ok())
+}
+
+/// Sets up a day that isn't written yet, ready to fill in.
+fn new_day(registry: &Registry, day: u32, config: &Config) -> bool {
+    if registry.get(day).is_some() {
+        eprintln!("Day {} already exists", day);
+        return false;
+    }
+    let title = config.title.as_deref().unwrap_or("Untitled");
+    match advent2020::scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day, title) {
+        Ok(created) => {
+            for path in created {
+                println!("Created {}", path.display());
+            }
+            println!("Registered day {} in src/days/mod.rs", day);
+            true
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
 }
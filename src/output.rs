@@ -0,0 +1,231 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answers::Check;
+use crate::bench::Stats;
+use crate::history::Comparison;
+use crate::solver::Answer;
+
+/// How results are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Coloured text for people.
+    Text,
+    /// One JSON object per line for scripts, with no escape codes.
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The result of one part of one day, as written out in JSON.
+pub struct Record<'a> {
+    pub day: u32,
+    pub part: u32,
+    pub answer: &'a Answer,
+    pub duration: Duration,
+    /// `None` when the input came from stdin.
+    pub input: Option<&'a Path>,
+}
+
+impl Record<'_> {
+    /// A single line of JSON, e.g.
+    /// `{"day":7,"part":1,"answer":169,"duration_us":512,"input":"data/data7.txt"}`
+    pub fn to_json(&self) -> String {
+        let answer = match self.answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => json_string(s),
+        };
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"duration_us\":{},\"input\":{}}}",
+            self.day,
+            self.part,
+            answer,
+            self.duration.as_micros(),
+            json_path(self.input)
+        )
+    }
+}
+
+/// Why a day gave no answers for an input, as written out in JSON in place of its `Record`s.
+pub struct ErrorRecord<'a> {
+    pub day: u32,
+    pub error: &'a str,
+    pub input: Option<&'a Path>,
+}
+
+impl ErrorRecord<'_> {
+    /// e.g. `{"day":7,"error":"timed out after 2.0s","input":"inputs/bob.txt"}`
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"error\":{},\"input\":{}}}",
+            self.day,
+            json_string(self.error),
+            json_path(self.input)
+        )
+    }
+}
+
+/// The benchmark of one phase of one day, as written out in JSON.
+pub struct BenchRecord<'a> {
+    pub day: u32,
+    /// `parse`, `part1` or `part2`.
+    pub phase: &'a str,
+    pub stats: Stats,
+    pub input: Option<&'a Path>,
+}
+
+impl BenchRecord<'_> {
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"phase\":{},\"runs\":{},\"min_us\":{},\"median_us\":{},\"mean_us\":{},\"p95_us\":{},\"input\":{}}}",
+            self.day,
+            json_string(self.phase),
+            self.stats.runs,
+            self.stats.min.as_micros(),
+            self.stats.median.as_micros(),
+            self.stats.mean.as_micros(),
+            self.stats.p95.as_micros(),
+            json_path(self.input)
+        )
+    }
+}
+
+/// How one phase of one day changed between benchmarked revisions, as written out in JSON.
+pub struct CompareRecord<'a> {
+    pub comparison: &'a Comparison,
+    pub regressed: bool,
+}
+
+impl CompareRecord<'_> {
+    /// e.g. `{"day":15,"phase":"part2","baseline_us":900,"current_us":1200,"change_pct":33.3,"regressed":true}`
+    pub fn to_json(&self) -> String {
+        let c = self.comparison;
+        format!(
+            "{{\"day\":{},\"phase\":{},\"baseline_us\":{},\"current_us\":{},\"change_pct\":{:.1},\"regressed\":{}}}",
+            c.day,
+            json_string(&c.phase),
+            c.baseline.as_micros(),
+            c.current.as_micros(),
+            c.change_pct(),
+            self.regressed
+        )
+    }
+}
+
+/// The verification of one part of one day, as written out in JSON.
+pub struct VerifyRecord<'a> {
+    pub day: u32,
+    pub part: u32,
+    pub check: &'a Check,
+    pub input: Option<&'a Path>,
+}
+
+impl VerifyRecord<'_> {
+    /// e.g. `{"day":7,"part":2,"status":"fail","expected":"82372","actual":"82373","input":"data/data7.txt"}`,
+    /// with `expected` and `actual` only there for failures.
+    pub fn to_json(&self) -> String {
+        let diff = match self.check {
+            Check::Fail { expected, actual } => {
+                format!(",\"expected\":{},\"actual\":{}", json_string(expected), json_string(actual))
+            }
+            _ => String::new(),
+        };
+        format!(
+            "{{\"day\":{},\"part\":{},\"status\":{}{},\"input\":{}}}",
+            self.day,
+            self.part,
+            json_string(self.check.name()),
+            diff,
+            json_path(self.input)
+        )
+    }
+}
+
+/// Quotes and escapes a string for JSON.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_path(path: Option<&Path>) -> String {
+    match path {
+        Some(path) => json_string(&path.display().to_string()),
+        None => String::from("null"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        assert_eq!("\"a\\\"b\\\\c\\n\\u0001\u{2B50}\"", json_string("a\"b\\c\n\u{1}\u{2B50}"));
+
+        let answer = Answer::from(169);
+        let record = Record {
+            day: 7,
+            part: 1,
+            answer: &answer,
+            duration: Duration::from_micros(512),
+            input: Some(Path::new("data/data7.txt")),
+        };
+        assert_eq!(
+            r#"{"day":7,"part":1,"answer":169,"duration_us":512,"input":"data/data7.txt"}"#,
+            record.to_json()
+        );
+
+        let answer = Answer::from("abc");
+        let record = Record { input: None, answer: &answer, ..record };
+        assert_eq!(
+            r#"{"day":7,"part":1,"answer":"abc","duration_us":512,"input":null}"#,
+            record.to_json()
+        );
+
+        let record = ErrorRecord {
+            day: 24,
+            error: "day 24, line 1, column 4: expected e, se, sw, w, nw or ne",
+            input: Some(Path::new("inputs/bad.txt")),
+        };
+        assert_eq!(
+            r#"{"day":24,"error":"day 24, line 1, column 4: expected e, se, sw, w, nw or ne","input":"inputs/bad.txt"}"#,
+            record.to_json()
+        );
+
+        let comparison = Comparison {
+            day: 15,
+            phase: String::from("part2"),
+            baseline: Duration::from_micros(900),
+            current: Duration::from_micros(1200),
+        };
+        let record = CompareRecord {
+            comparison: &comparison,
+            regressed: true,
+        };
+        assert_eq!(
+            r#"{"day":15,"phase":"part2","baseline_us":900,"current_us":1200,"change_pct":33.3,"regressed":true}"#,
+            record.to_json()
+        );
+    }
+}
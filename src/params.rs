@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+/// The numbers baked into the puzzles themselves rather than their inputs, read from a file like `data/params.txt`:
+///
+/// ```text
+/// # day.name    value
+/// 15.part2_turns = 30000000
+/// 7.bag = shiny gold
+/// ```
+///
+/// A day uses its own default for anything not given, so the file only needs what's different. Any day can also be
+/// given its own `timeout` in seconds, e.g. `23.timeout = 30`, in place of `--timeout`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Params {
+    values: HashMap<(u32, String), String>,
+}
+
+/// One of the parameters a day reads, and which values it can take.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    /// Whether a value will do, e.g. [`positive`] for a number of turns. Checked as the value is given, so a day can
+    /// rely on it when it comes to read the parameter.
+    pub valid: fn(&str) -> bool,
+}
+
+/// The parameters the days read, once `set`. Until then every day uses its defaults.
+static PARAMS: RwLock<Option<Params>> = RwLock::new(None);
+
+impl Params {
+    pub fn load(path: &Path) -> Result<Params, String> {
+        let text = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        Params::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn parse(text: &str) -> Result<Params, String> {
+        let mut params = Params::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            params.apply(line).map_err(|err| format!("line {}: {}", i + 1, err))?;
+        }
+        Ok(params)
+    }
+
+    /// Sets one parameter from e.g. `15.part2_turns=1000000`, replacing any value it already had. The day has to read
+    /// a parameter of that name, and the value has to suit it, so a typo is caught rather than quietly running with the
+    /// default or stopping the day part way.
+    pub fn apply(&mut self, assignment: &str) -> Result<(), String> {
+        let (day, name, value) = parse_assignment(assignment)?;
+        let param = find(day, name)?;
+        if !(param.valid)(value) {
+            return Err(format!("day {} parameter {} can't be {:?}", day, name, value));
+        }
+        self.values.insert((day, name.to_string()), value.to_string());
+        Ok(())
+    }
+
+    /// The day's own `timeout`, if it has one.
+    pub fn timeout(&self, day: u32) -> Option<Duration> {
+        self.value(day, "timeout").and_then(|secs| secs.parse().ok()).map(Duration::from_secs_f64)
+    }
+
+    /// A hash of the values given for the day's parameters, which changes with any of them that could change its
+    /// answers or how long they take.
+    pub fn fingerprint(&self, day: u32) -> u64 {
+        let mut given: Vec<String> = self
+            .values
+            .iter()
+            .filter(|((d, name), _)| *d == day && name != TIMEOUT.name)
+            .map(|((_, name), value)| format!("{}={}\n", name, value))
+            .collect();
+        given.sort_unstable();
+        crate::input_hash(&given.concat())
+    }
+
+    /// The value given for one of a day's parameters, if any.
+    pub fn value(&self, day: u32, name: &str) -> Option<&str> {
+        self.values.get(&(day, name.to_string())).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Splits `<day>.<name> = <value>` into its parts.
+fn parse_assignment(assignment: &str) -> Result<(u32, &str, &str), String> {
+    let expected = || format!("expected <day>.<name> = <value>, not {}", assignment);
+    let (key, value) = assignment.split_once('=').ok_or_else(expected)?;
+    let (day, name) = key.trim().split_once('.').ok_or_else(expected)?;
+    let day = day.parse().map_err(|_| expected())?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(expected());
+    }
+    Ok((day, name, value.trim()))
+}
+
+/// The parameter a day reads by that name.
+fn find(day: u32, name: &str) -> Result<Param, String> {
+    // Every day's parameters, looked up once rather than for each one given.
+    static KNOWN: OnceLock<HashMap<u32, &'static [Param]>> = OnceLock::new();
+    let known = KNOWN.get_or_init(|| crate::days::registry().iter().map(|puzzle| (puzzle.id(), puzzle.params())).collect());
+    let params = known.get(&day).copied().unwrap_or_default();
+    if name == TIMEOUT.name && known.contains_key(&day) {
+        return Ok(TIMEOUT);
+    }
+    if let Some(param) = params.iter().find(|param| param.name == name) {
+        Ok(*param)
+    } else if params.is_empty() {
+        Err(format!("day {} has no parameters, so no {}", day, name))
+    } else {
+        let names: Vec<&str> = params.iter().map(|param| param.name).collect();
+        Err(format!("day {} has no parameter {} (it has {})", day, name, names.join(", ")))
+    }
+}
+
+/// Every day has a time budget, whether or not it has any parameters of its own.
+const TIMEOUT: Param = Param { name: "timeout", valid: seconds };
+
+/// A value that reads as a `T`, for [`Param::valid`].
+pub fn reads_as<T: FromStr>(value: &str) -> bool {
+    value.parse::<T>().is_ok()
+}
+
+/// A number above zero, e.g. a count of turns.
+pub fn positive<T: FromStr + Default + PartialOrd>(value: &str) -> bool {
+    value.parse::<T>().is_ok_and(|n| n > T::default())
+}
+
+/// A positive number of seconds, which can be a fraction.
+pub fn seconds(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(|secs| secs > 0.0 && secs.is_finite())
+}
+
+/// A list of at least one `T`, as [`get_list`] reads it.
+pub fn list_of<T: FromStr>(value: &str) -> bool {
+    let mut items = list_items(value).peekable();
+    items.peek().is_some() && items.all(reads_as::<T>)
+}
+
+fn list_items(value: &str) -> impl Iterator<Item = &str> {
+    value.split(|c: char| c == ',' || c.is_whitespace()).filter(|item| !item.is_empty())
+}
+
+/// Makes `params` the ones the days read from now on, on every thread.
+pub fn set(params: Params) {
+    *PARAMS.write().unwrap() = Some(params);
+}
+
+/// The day's own time budget, if it was given one.
+pub fn timeout(day: u32) -> Option<Duration> {
+    PARAMS.read().unwrap().as_ref().and_then(|p| p.timeout(day))
+}
+
+/// The fingerprint of the day's parameters, see [`Params::fingerprint`].
+pub fn fingerprint(day: u32) -> u64 {
+    PARAMS.read().unwrap().as_ref().map_or_else(|| Params::default().fingerprint(day), |p| p.fingerprint(day))
+}
+
+/// One of a day's parameters, or `default` if it wasn't given.
+///
+/// The value was checked against the day's [`Param::valid`] when it was given, so it reads as a `T` as long as that
+/// checks for the same `T`.
+pub fn get<T: FromStr>(day: u32, name: &str, default: T) -> T {
+    match PARAMS.read().unwrap().as_ref().and_then(|p| p.value(day, name)) {
+        Some(value) => value.parse().unwrap_or_else(|_| unchecked(day, name, value)),
+        None => default,
+    }
+}
+
+/// A parameter holding a list, separated by commas and/or spaces, or `default` if it wasn't given.
+pub fn get_list<T: FromStr + Clone>(day: u32, name: &str, default: &[T]) -> Vec<T> {
+    match PARAMS.read().unwrap().as_ref().and_then(|p| p.value(day, name)) {
+        Some(value) => list_items(value)
+            .map(|item| item.parse().unwrap_or_else(|_| unchecked(day, name, value)))
+            .collect(),
+        None => default.to_vec(),
+    }
+}
+
+fn unchecked(day: u32, name: &str, value: &str) -> ! {
+    panic!("Day {} parameter {} was let through as {:?} but isn't the type the day reads", day, name, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let params = Params::parse("# Comment\n\n15.part2_turns = 1000\n7.bag=  dark orange \n 3.slopes = 1/1, 3/1\n")
+            .unwrap();
+        assert_eq!(3, params.len());
+        assert_eq!(Some("1000"), params.value(15, "part2_turns"));
+        assert_eq!(Some("dark orange"), params.value(7, "bag"));
+        assert_eq!(Some("1/1, 3/1"), params.value(3, "slopes"));
+        assert_eq!(None, params.value(15, "part1_turns"));
+        assert_eq!(None, params.value(7, "part2_turns"));
+
+        assert!(Params::parse("15.part2_turns 1000\n").is_err());
+        assert!(Params::parse("part2_turns = 1000\n").is_err());
+        assert!(Params::parse("x.turns = 1000\n").is_err());
+        assert!(Params::parse("15. = 1000\n").is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut params = Params::parse("15.part2_turns = 1000\n").unwrap();
+        params.apply("15.part2_turns=50").unwrap();
+        params.apply("23.cups=9").unwrap();
+        assert_eq!(Some("50"), params.value(15, "part2_turns"));
+        assert_eq!(Some("9"), params.value(23, "cups"));
+        assert!(params.apply("23.cups").is_err());
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        let mut params = Params::default();
+        let err = params.apply("15.part2_turn=10").unwrap_err();
+        assert_eq!("day 15 has no parameter part2_turn (it has part1_turns, part2_turns)", err);
+        assert!(params.apply("2.total=10").is_err());
+        assert!(params.apply("99.window=10").is_err());
+        assert!(Params::parse("9.window = 5\n9.windw = 5\n").is_err());
+        // Every parameter the days read is in the example file.
+        let example = Params::load(Path::new("data/params.txt")).unwrap();
+        let known: usize = crate::days::registry().iter().map(|puzzle| puzzle.params().len()).sum();
+        assert_eq!(known, example.len());
+    }
+
+    #[test]
+    fn test_bad_values() {
+        let mut params = Params::default();
+        assert_eq!("day 23 parameter cups can't be \"abc\"", params.apply("23.cups=abc").unwrap_err());
+        assert!(params.apply("9.window=0").is_err());
+        assert!(params.apply("15.part2_turns=-5").is_err());
+        assert!(params.apply("3.slope=3/0").is_err());
+        assert!(params.apply("3.slopes=1/1, 3/x").is_err());
+        assert!(params.apply("3.slopes=,").is_err());
+        assert!(Params::parse("9.window = 5\n17.cycles = six\n").unwrap_err().starts_with("line 2: "));
+        assert!(params.is_empty());
+
+        params.apply("3.slopes=1/1 3/2").unwrap();
+        params.apply("7.bag=dark orange").unwrap();
+        assert_eq!(2, params.len());
+    }
+
+    #[test]
+    fn test_timeout() {
+        // Days without parameters of their own can still have a timeout.
+        let params = Params::parse("2.timeout = 1.5\n23.timeout = 30\n").unwrap();
+        assert_eq!(Some(Duration::from_millis(1500)), params.timeout(2));
+        assert_eq!(Some(Duration::from_secs(30)), params.timeout(23));
+        assert_eq!(None, params.timeout(15));
+        assert!(Params::parse("23.timeout = 0\n").is_err());
+        assert!(Params::parse("23.timeout = inf\n").is_err());
+        assert!(Params::parse("23.timeout = soon\n").is_err());
+        assert!(Params::parse("99.timeout = 5\n").is_err());
+        assert_eq!(None, timeout(99));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let params = Params::parse("23.cups = 9\n23.part2_moves = 100\n15.part2_turns = 1000\n").unwrap();
+        let reordered = Params::parse("23.part2_moves = 100\n23.cups = 9\n23.timeout = 5\n").unwrap();
+        assert_eq!(params.fingerprint(23), reordered.fingerprint(23));
+        assert_ne!(params.fingerprint(15), reordered.fingerprint(15));
+        assert_eq!(Params::default().fingerprint(15), reordered.fingerprint(15));
+        let other = Params::parse("23.cups = 10\n23.part2_moves = 100\n").unwrap();
+        assert_ne!(params.fingerprint(23), other.fingerprint(23));
+    }
+
+    #[test]
+    fn test_defaults() {
+        // Nothing sets parameters for day 99, whatever else the tests are doing.
+        assert_eq!(25, get(99, "window", 25));
+        assert_eq!(vec![1, 2], get_list(99, "slopes", &[1, 2]));
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// How many workers to use when not told otherwise. Always at least two, so one slow day can't hold up the rest.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(2, |n| n.get().max(2))
+}
+
+/// Maps `f` over `items` on `jobs` worker threads, returning the results in the same order as the items.
+///
+/// Workers take the next item as soon as they finish one, so a few long items don't stop the short ones.
+pub fn map_parallel<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let workers = jobs.clamp(1, items.len().max(1));
+
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(i) {
+                            Some(item) => done.push((i, f(item))),
+                            None => return done,
+                        }
+                    }
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_parallel() {
+        let items: Vec<u64> = (0..20).collect();
+        let squares = map_parallel(&items, 4, |x| {
+            // The early items are slowest, so they finish last.
+            thread::sleep(Duration::from_millis(20 - x));
+            x * x
+        });
+        assert_eq!(items.iter().map(|x| x * x).collect::<Vec<_>>(), squares);
+
+        assert_eq!(vec![2], map_parallel(&[1], 8, |x| x * 2));
+        assert!(map_parallel(&[] as &[u32], 3, |x| x * 2).is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use termion::clear;
+
+use crate::bench::fmt_micros;
+
+/// How long-running loops show how they're getting on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Not at all, e.g. when several days run at once or in the dashboard.
+    Off = 0,
+    /// A bar on stderr, redrawn in place, for a terminal.
+    Bar = 1,
+    /// A line on stderr every few seconds, for a log file.
+    Lines = 2,
+}
+
+impl Mode {
+    /// A bar if stderr is a terminal, or lines if it's going somewhere else.
+    pub fn for_stderr() -> Mode {
+        if termion::is_tty(&io::stderr()) {
+            Mode::Bar
+        } else {
+            Mode::Lines
+        }
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(Mode::Off as u8);
+
+/// Nothing is shown until this long into a loop, so quick runs stay quiet.
+const QUIET_FOR: Duration = Duration::from_millis(500);
+const BAR_EVERY: Duration = Duration::from_millis(100);
+const LINE_EVERY: Duration = Duration::from_secs(2);
+const BAR_WIDTH: u64 = 30;
+
+/// Sets how progress is shown from now on, on every thread. Until then it isn't.
+pub fn set_mode(mode: Mode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => Mode::Bar,
+        2 => Mode::Lines,
+        _ => Mode::Off,
+    }
+}
+
+/// The progress of one long loop, e.g. the turns of Day 15's memory game.
+///
+/// Loops call `update` every so often, usually alongside `cancel::check()`, and it decides for itself whether anything
+/// needs showing, so it costs next to nothing when progress is off or the loop is quick. Dropping it, however the loop
+/// ends, takes the bar off the screen.
+pub struct Progress {
+    label: &'static str,
+    /// How many iterations there'll be, if that's known up front.
+    total: Option<u64>,
+    mode: Mode,
+    start: Instant,
+    shown: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: Option<u64>) -> Self {
+        Progress {
+            label,
+            total,
+            mode: mode(),
+            start: Instant::now(),
+            shown: None,
+        }
+    }
+
+    /// Notes that `done` iterations are done, showing them if it's been long enough since last time.
+    pub fn update(&mut self, done: u64) {
+        let every = match self.mode {
+            Mode::Off => return,
+            Mode::Bar => BAR_EVERY,
+            Mode::Lines => LINE_EVERY,
+        };
+        let now = Instant::now();
+        let due = match self.shown {
+            None => now - self.start >= QUIET_FOR,
+            Some(last) => now - last >= every,
+        };
+        if !due {
+            return;
+        }
+        self.shown = Some(now);
+
+        let text = status(self.label, done, self.total, now - self.start, self.mode == Mode::Bar);
+        if self.mode == Mode::Bar {
+            eprint!("\r{}{}", clear::CurrentLine, text);
+            io::stderr().flush().ok();
+        } else {
+            eprintln!("{}", text);
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.mode == Mode::Bar && self.shown.is_some() {
+            eprint!("\r{}", clear::CurrentLine);
+            io::stderr().flush().ok();
+        }
+    }
+}
+
+/// A line saying how far a loop has got, e.g. `Day 15 turns [#####...] 5000000/30000000 16%, 10.0M/s, ETA 2.500s`.
+fn status(label: &str, done: u64, total: Option<u64>, elapsed: Duration, bar: bool) -> String {
+    let rate = done as f64 / elapsed.as_secs_f64().max(1e-9);
+    match total {
+        Some(total) if total > 0 => {
+            let done = done.min(total);
+            let mut text = String::from(label);
+            if bar {
+                let filled = (done * BAR_WIDTH / total) as usize;
+                text.push_str(&format!(" [{}{}]", "#".repeat(filled), ".".repeat(BAR_WIDTH as usize - filled)));
+            }
+            let eta = if rate > 0.0 {
+                fmt_micros(Duration::from_secs_f64((total - done) as f64 / rate))
+            } else {
+                String::from("?")
+            };
+            format!(
+                "{} {}/{} {}%, {}/s, ETA {}",
+                text,
+                done,
+                total,
+                done * 100 / total,
+                fmt_count(rate),
+                eta
+            )
+        }
+        _ => format!("{} {}, {}/s", label, done, fmt_count(rate)),
+    }
+}
+
+/// A rate in thousands or millions, e.g. `12.3M`.
+fn fmt_count(n: f64) -> String {
+    if n >= 1e6 {
+        format!("{:.1}M", n / 1e6)
+    } else if n >= 1e3 {
+        format!("{:.1}k", n / 1e3)
+    } else {
+        format!("{:.0}", n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            "Day 15 turns [######........................] 6000000/30000000 20%, 6.0M/s, ETA 4.000s",
+            status("Day 15 turns", 6_000_000, Some(30_000_000), second, true)
+        );
+        assert_eq!(
+            "Day 23 moves 2500/10000 25%, 1.2k/s, ETA 6.000s",
+            status("Day 23 moves", 2500, Some(10_000), 2 * second, false)
+        );
+        assert_eq!("Day 11 rounds 42, 42/s", status("Day 11 rounds", 42, None, second, false));
+        assert_eq!("Day 17 cycles 0/6 0%, 0/s, ETA ?", status("Day 17 cycles", 0, Some(6), second, false));
+    }
+
+    #[test]
+    fn test_quiet_when_off() {
+        // Progress is off unless something turns it on, so the days' tests never draw anything.
+        assert_eq!(Mode::Off, Progress::new("Day 99 steps", Some(10)).mode);
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::Write;
+
+use crate::bench::fmt_micros;
+use crate::solver::{Solution, Timings};
+
+/// How one day went, as written up in the report.
+pub struct ReportEntry {
+    pub day: u32,
+    pub title: String,
+    pub result: Result<(Solution, Timings), String>,
+    /// Tells whose input the answers are for, or `None` if it couldn't be read.
+    pub input_hash: Option<u64>,
+    /// The day's picture of its working, if it has one and it was asked for.
+    pub visual: Option<String>,
+}
+
+/// The entries as a Markdown document, e.g. for pasting into a wiki: a table of every day's answers and timings, then
+/// any pictures, each under its own heading.
+pub fn render_report(entries: &[ReportEntry], revision: &str) -> String {
+    let failed = entries.iter().filter(|e| e.result.is_err()).count();
+    let total = entries.iter().filter_map(|e| e.result.as_ref().ok()).map(|(_, t)| t.total()).sum();
+
+    let mut doc = String::from("# Advent of Code 2020 results\n\n");
+    writeln!(
+        doc,
+        "{} days at revision `{}`, {} failed, {} solving in all.\n",
+        entries.len(),
+        revision,
+        failed,
+        fmt_micros(total)
+    )
+    .unwrap();
+
+    doc.push_str("| Day | Title | Part 1 | Part 2 | Time | Status | Input |\n");
+    doc.push_str("|---:|---|---:|---:|---:|---|---|\n");
+    for entry in entries {
+        let (part1, part2, time, status) = match &entry.result {
+            Ok((solution, timings)) => (
+                solution.part1.as_ref().map(ToString::to_string).unwrap_or_default(),
+                solution.part2.as_ref().map(ToString::to_string).unwrap_or_default(),
+                fmt_micros(timings.total()),
+                String::from("ok"),
+            ),
+            Err(msg) => (String::new(), String::new(), String::new(), msg.clone()),
+        };
+        let input = entry.input_hash.map(|h| format!("`{:016x}`", h)).unwrap_or_default();
+        let cells = [entry.day.to_string(), entry.title.clone(), part1, part2, time, status, input];
+        let cells: Vec<String> = cells.iter().map(|cell| cell_text(cell)).collect();
+        writeln!(doc, "| {} |", cells.join(" | ")).unwrap();
+    }
+
+    for entry in entries {
+        if let Some(visual) = &entry.visual {
+            write!(doc, "\n## Day {}: {}\n\n```text\n{}", entry.day, entry.title, visual).unwrap();
+            if !visual.ends_with('\n') {
+                doc.push('\n');
+            }
+            doc.push_str("```\n");
+        }
+    }
+    doc
+}
+
+/// Keeps a table cell on one line, and its pipes from starting another cell.
+fn cell_text(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// The 64 bit FNV-1a hash of an input. It's only for telling inputs apart, so nothing stronger is needed.
+pub fn input_hash(data: &str) -> u64 {
+    data.bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Answer;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render_report() {
+        let solved = Solution {
+            part1: Some(Answer::from(514_579)),
+            part2: Some(Answer::from("a|b")),
+        };
+        let timings = Timings {
+            parse: Duration::from_micros(100),
+            part1: Duration::from_micros(200),
+            part2: Duration::from_micros(1200),
+        };
+        let entries = vec![
+            ReportEntry {
+                day: 1,
+                title: String::from("Report Repair"),
+                result: Ok((solved, timings)),
+                input_hash: Some(0xabc),
+                visual: None,
+            },
+            ReportEntry {
+                day: 11,
+                title: String::from("Seating System"),
+                result: Err(String::from("timed out after 1.0s")),
+                input_hash: None,
+                visual: Some(String::from("#.L\nL.#")),
+            },
+        ];
+        let doc = render_report(&entries, "3142aaa");
+        assert!(doc.contains("2 days at revision `3142aaa`, 1 failed, 1.500ms solving in all."));
+        assert!(doc.contains("\n| 1 | Report Repair | 514579 | a\\|b | 1.500ms | ok | `0000000000000abc` |\n"));
+        assert!(doc.contains("\n| 11 | Seating System |  |  |  | timed out after 1.0s |  |\n"));
+        assert!(doc.ends_with("\n## Day 11: Seating System\n\n```text\n#.L\nL.#\n```\n"));
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, input_hash(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, input_hash("a"));
+        assert_ne!(input_hash("1\n2\n"), input_hash("2\n1\n"));
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A small, seedable random number generator (SplitMix64) for making up puzzle inputs.
+///
+/// It's nowhere near good enough for anything that matters, but the same seed always gives the same input on every
+/// platform, which is what we want for reproducing a failure.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// A seed that's different every time, for when none was asked for.
+    pub fn random_seed() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64 ^ u64::from(std::process::id()))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to but not including `n`, which mustn't be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Nothing is below 0.");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number from `lo` to `hi` inclusive.
+    pub fn between(&mut self, lo: u64, hi: u64) -> u64 {
+        assert!(lo <= hi, "Empty range {}..={}.", lo, hi);
+        match (hi - lo).checked_add(1) {
+            Some(span) => lo + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        // The top 53 bits make an evenly spread f64 in [0, 1).
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < p
+    }
+
+    /// A lowercase ASCII letter.
+    pub fn letter(&mut self) -> char {
+        char::from(b'a' + self.below(26) as u8)
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(2020);
+        let mut b = Rng::new(2020);
+        let xs: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(xs, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        let mut c = Rng::new(2021);
+        assert_ne!(xs, (0..10).map(|_| c.next_u64()).collect::<Vec<_>>());
+
+        for _ in 0..1000 {
+            assert!(a.below(7) < 7);
+            assert!((3..=5).contains(&a.between(3, 5)));
+        }
+        assert!(!a.chance(0.0));
+        assert!(a.chance(1.0));
+
+        let mut items: Vec<u32> = (0..20).collect();
+        a.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!((0..20).collect::<Vec<_>>(), items);
+    }
+}
@@ -0,0 +1,126 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Sets up a new day in the crate at `root`: `src/days/dayN.rs` from `src/day_template.rs`, an empty
+/// `data/dataN.txt` for its test to read, and the `mod` line and registration in `src/days/mod.rs`.
+///
+/// Nothing here knows about 2020. It only needs the template and a `days/mod.rs` with a `registry()`, even one with no
+/// days in it yet, so it works just as well to start next year's crate. It refuses to overwrite anything, and checks
+/// that before touching any files. Gives the files it created.
+pub fn new_day(root: &Path, day: u32, title: &str) -> Result<Vec<PathBuf>, String> {
+    let template_path = root.join("src").join("day_template.rs");
+    let mod_path = root.join("src").join("days").join("mod.rs");
+    let day_path = root.join("src").join("days").join(format!("day{}.rs", day));
+    let data_path = root.join("data").join(format!("data{}.txt", day));
+
+    for path in &[&day_path, &data_path] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+    let read = |path: &Path| fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err));
+    let template = read(&template_path)?;
+    let days = register(&read(&mod_path)?, day).map_err(|err| format!("{}: {}", mod_path.display(), err))?;
+
+    create(&day_path, &fill_template(&template, day, title))?;
+    create(&data_path, "")?;
+    fs::write(&mod_path, days).map_err(|err| format!("Could not write {}: {}", mod_path.display(), err))?;
+    Ok(vec![day_path, data_path])
+}
+
+/// Writes a new file, failing if it's already there.
+fn create(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|err| format!("Could not create {}: {}", path.display(), err))
+}
+
+/// The template with `<DAY>` and `<TITLE>` filled in.
+pub fn fill_template(template: &str, day: u32, title: &str) -> String {
+    template.replace("<DAY>", &day.to_string()).replace("<TITLE>", &title.escape_default().to_string())
+}
+
+/// Adds `pub mod dayN;` and its registration to the text of `days/mod.rs`, each in order among the days already there.
+pub fn register(text: &str, day: u32) -> Result<String, String> {
+    let module = format!("pub mod day{};", day);
+    let registration = format!("    registry.register(day{}::Day{});", day, day);
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.iter().any(|line| line.trim() == module) {
+        return Err(format!("day {} is already a module", day));
+    }
+
+    // The day goes after the last one before it, or failing that, straight after `anchor`.
+    let place = |lines: &[&str], prefix: &str, anchor: &str| -> Option<usize> {
+        let mut at = lines.iter().position(|line| line.trim_start().starts_with(anchor))? + 1;
+        for (i, line) in lines.iter().enumerate().skip(at) {
+            match line.trim_start().strip_prefix(prefix).and_then(day_number) {
+                Some(d) if d < day => at = i + 1,
+                Some(_) => break,
+                None => {}
+            }
+        }
+        Some(at)
+    };
+
+    let at = place(&lines, "pub mod day", "use ").ok_or("no `use` line to put the module after")?;
+    lines.insert(at, &module);
+    let at = place(&lines, "registry.register(day", "let mut registry")
+        .ok_or("no `let mut registry` in a registry() function")?;
+    lines.insert(at, &registration);
+
+    let mut days = lines.join("\n");
+    days.push('\n');
+    Ok(days)
+}
+
+/// The day number at the start of e.g. `12;` or `12::Day12);`.
+fn day_number(rest: &str) -> Option<u32> {
+    let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    rest[..digits].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS: &str = "//! The days.\nuse crate::Registry;\n\npub mod day1;\npub mod day3;\n\n/// Every day.\n\
+                        pub fn registry() -> Registry {\n    let mut registry = Registry::new();\n    \
+                        registry.register(day1::Day1);\n    registry.register(day3::Day3);\n    registry\n}\n";
+
+    #[test]
+    fn test_register() {
+        let days = register(DAYS, 2).unwrap();
+        assert!(days.contains("pub mod day1;\npub mod day2;\npub mod day3;\n"));
+        assert!(days.contains("(day1::Day1);\n    registry.register(day2::Day2);\n    registry.register(day3::Day3);"));
+
+        let days = register(&days, 12).unwrap();
+        assert!(days.contains("pub mod day3;\npub mod day12;\n\n"));
+        assert!(days.contains("(day3::Day3);\n    registry.register(day12::Day12);\n    registry\n"));
+        assert!(register(&days, 12).is_err());
+
+        // A new year, with no days yet.
+        let empty = "use crate::Registry;\n\npub fn registry() -> Registry {\n    let mut registry = Registry::new();\n    \
+                     registry\n}\n";
+        let days = register(empty, 1).unwrap();
+        assert!(days.starts_with("use crate::Registry;\npub mod day1;\n"));
+        assert!(days.contains("Registry::new();\n    registry.register(day1::Day1);\n    registry\n"));
+
+        assert!(register("pub mod day1;\n", 2).is_err());
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let template = "pub struct Day<DAY>;\nfn title() -> &'static str {\n    \"<TITLE>\"\n}\n";
+        assert_eq!(
+            "pub struct Day26;\nfn title() -> &'static str {\n    \"The \\\"Big\\\" One\"\n}\n",
+            fill_template(template, 26, "The \"Big\" One")
+        );
+    }
+}
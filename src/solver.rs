@@ -0,0 +1,396 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::bench::Samples;
+use crate::error::ParseError;
+use crate::input;
+use crate::params::Param;
+use crate::rng::Rng;
+
+/// The answer to one part of a puzzle. Most days produce a number, but a few produce a code or a list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Number(i64::from(n))
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Number(i64::from(n))
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        // Anything too big for an i64 is still a perfectly good answer, just not one we can do sums with.
+        i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        i64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Each day implements this to plug into the registry.
+///
+/// The input is parsed once into a shape shared by both parts, so each part only does its own work. Anything wrong with
+/// the input should be found while parsing, so the parts can trust what they're given. Run through a `Puzzle`, `parse`
+/// sees the input after `input::normalise`, so it needn't worry about line endings or stray whitespace.
+pub trait Solver {
+    type Input;
+
+    fn id(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn parse(&self, data: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+
+    /// Makes up a valid input for this day, about `scale` big (lines, tiles, bags... whatever suits the day), or its
+    /// usual size for `None`. Returns `None` if the day has no generator.
+    fn generate(&self, _rng: &mut Rng, _scale: Option<usize>) -> Option<String> {
+        None
+    }
+
+    /// Solves `part` the plain, obviously right way, however slow, to check the real solver against on small generated
+    /// inputs. Returns `None` for parts without a reference solver.
+    fn reference(&self, _input: &Self::Input, _part: u32) -> Option<Answer> {
+        None
+    }
+
+    /// A picture of the day's working for the report, e.g. the seat map once it settles, or `None` if it has none.
+    fn visualise(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    /// The parameters the day reads with `params::get`, e.g. `part2_turns`, and the values each can take. Nothing else
+    /// can be set for it.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+}
+
+/// Which parts of a day to run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: u32) -> bool {
+        matches!((self, part), (Parts::One, 1) | (Parts::Two, 2) | (Parts::Both, 1..=2))
+    }
+}
+
+/// A day's answers, for whichever parts were run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Solution {
+    /// The answers that were worked out, numbered by part.
+    pub fn answers(&self) -> impl Iterator<Item = (u32, &Answer)> {
+        vec![(1, &self.part1), (2, &self.part2)].into_iter().filter_map(|(part, answer)| Some((part, answer.as_ref()?)))
+    }
+}
+
+/// How long each phase of a day took.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// A type-erased `Solver`, so days with different inputs can live side by side in the registry.
+///
+/// Puzzles are shared between threads when running every day at once, hence `Sync`.
+pub trait Puzzle: Sync {
+    fn id(&self) -> u32;
+    fn title(&self) -> &'static str;
+    /// Parses the input once and solves the chosen parts from it. Parts that weren't run have no answer and no time.
+    fn solve_timed(&self, data: &str, parts: Parts) -> Result<(Solution, Timings), ParseError>;
+
+    /// Times each phase `runs` times, after `warmup` untimed runs of each, skipping the parts that weren't chosen.
+    fn bench(&self, data: &str, warmup: usize, runs: usize, parts: Parts) -> Result<Samples, ParseError>;
+
+    /// See [`Solver::generate`].
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String>;
+
+    /// Solves each part that has a reference solver both ways, giving the part, the real answer and the reference one.
+    fn cross_check(&self, data: &str) -> Result<Vec<(u32, Answer, Answer)>, ParseError>;
+
+    /// Parses the input and draws it. See [`Solver::visualise`].
+    fn visualise(&self, data: &str) -> Result<Option<String>, ParseError>;
+
+    /// See [`Solver::params`].
+    fn params(&self) -> &'static [Param];
+
+    fn solve(&self, data: &str) -> Result<Solution, ParseError> {
+        self.solve_timed(data, Parts::Both).map(|(solution, _)| solution)
+    }
+}
+
+impl<S: Solver + Sync> Puzzle for S {
+    fn id(&self) -> u32 {
+        Solver::id(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solver::title(self)
+    }
+
+    fn solve_timed(&self, data: &str, parts: Parts) -> Result<(Solution, Timings), ParseError> {
+        let data = input::normalise(data);
+        let start = Instant::now();
+        let input = self.parse(&data)?;
+        let mut timings = Timings {
+            parse: start.elapsed(),
+            ..Timings::default()
+        };
+
+        let solve = |part: u32, f: &dyn Fn(&S::Input) -> Answer, time: &mut Duration| {
+            parts.includes(part).then(|| {
+                let start = Instant::now();
+                let answer = f(&input);
+                *time = start.elapsed();
+                answer
+            })
+        };
+        let part1 = solve(1, &|input| self.part1(input), &mut timings.part1);
+        let part2 = solve(2, &|input| self.part2(input), &mut timings.part2);
+        Ok((Solution { part1, part2 }, timings))
+    }
+
+    fn bench(&self, data: &str, warmup: usize, runs: usize, parts: Parts) -> Result<Samples, ParseError> {
+        fn time<T>(warmup: usize, runs: usize, f: impl Fn() -> T) -> Vec<Duration> {
+            for _ in 0..warmup {
+                f();
+            }
+            (0..runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    let result = f();
+                    let elapsed = start.elapsed();
+                    // Dropping the result (maybe a big parsed input) isn't part of the phase.
+                    drop(result);
+                    elapsed
+                })
+                .collect()
+        }
+
+        let data = input::normalise(data);
+        let input = self.parse(&data)?;
+        Ok(Samples {
+            parse: time(warmup, runs, || self.parse(&data)),
+            part1: if parts.includes(1) { time(warmup, runs, || self.part1(&input)) } else { vec![] },
+            part2: if parts.includes(2) { time(warmup, runs, || self.part2(&input)) } else { vec![] },
+        })
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: Option<usize>) -> Option<String> {
+        Solver::generate(self, rng, scale)
+    }
+
+    fn cross_check(&self, data: &str) -> Result<Vec<(u32, Answer, Answer)>, ParseError> {
+        let input = self.parse(&input::normalise(data))?;
+        let mut checked = vec![];
+        for part in 1..=2 {
+            if let Some(expected) = self.reference(&input, part) {
+                let actual = if part == 1 { self.part1(&input) } else { self.part2(&input) };
+                checked.push((part, actual, expected));
+            }
+        }
+        Ok(checked)
+    }
+
+    fn visualise(&self, data: &str) -> Result<Option<String>, ParseError> {
+        Ok(Solver::visualise(self, &self.parse(&input::normalise(data))?))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Solver::params(self)
+    }
+}
+
+/// All the known days, kept in day order.
+#[derive(Default)]
+pub struct Registry {
+    puzzles: Vec<Box<dyn Puzzle>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry { puzzles: vec![] }
+    }
+
+    /// Adds a day, replacing any previous solver with the same id.
+    pub fn register<S: Solver + Sync + 'static>(&mut self, solver: S) {
+        let id = Solver::id(&solver);
+        self.puzzles.retain(|p| p.id() != id);
+        self.puzzles.push(Box::new(solver));
+        self.puzzles.sort_by_key(|p| p.id());
+    }
+
+    pub fn get(&self, id: u32) -> Option<&dyn Puzzle> {
+        self.puzzles.iter().find(|p| p.id() == id).map(|p| p.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Puzzle> {
+        self.puzzles.iter().map(|p| p.as_ref())
+    }
+
+    pub fn ids(&self) -> Vec<u32> {
+        self.puzzles.iter().map(|p| p.id()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solver for Doubler {
+        type Input = Vec<i64>;
+
+        fn id(&self) -> u32 {
+            2
+        }
+
+        fn title(&self) -> &'static str {
+            "Doubler"
+        }
+
+        fn parse(&self, data: &str) -> Result<Self::Input, ParseError> {
+            data.lines()
+                .enumerate()
+                .map(|(i, x)| x.parse().map_err(|_| ParseError::at_line(2, data, i + 1, 1, "a number")))
+                .collect()
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            input.iter().map(|x| x * 2).sum::<i64>().into()
+        }
+
+        fn part2(&self, input: &Self::Input) -> Answer {
+            format!("{:?}", input).into()
+        }
+    }
+
+    struct Other;
+
+    impl Solver for Other {
+        type Input = ();
+
+        fn id(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Other"
+        }
+
+        fn parse(&self, _data: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &Self::Input) -> Answer {
+            0.into()
+        }
+
+        fn part2(&self, _input: &Self::Input) -> Answer {
+            0.into()
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register(Doubler);
+        registry.register(Other);
+        assert_eq!(vec![1, 2], registry.ids());
+
+        let doubler = registry.get(2).unwrap();
+        assert_eq!("Doubler", doubler.title());
+        assert_eq!(
+            Solution {
+                part1: Some(Answer::Number(12)),
+                part2: Some(Answer::Text(String::from("[1, 2, 3]")))
+            },
+            doubler.solve("1\n2\n3").unwrap()
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 2, 1, "two", "a number")),
+            doubler.solve("1\ntwo\n3")
+        );
+        assert!(registry.get(3).is_none());
+
+        let (solution, timings) = doubler.solve_timed("1\n2", Parts::Two).unwrap();
+        assert_eq!(None, solution.part1);
+        assert_eq!(Some(Answer::from("[1, 2]")), solution.part2);
+        assert_eq!(vec![(2, &Answer::from("[1, 2]"))], solution.answers().collect::<Vec<_>>());
+        assert_eq!(Duration::ZERO, timings.part1);
+        assert!(Parts::Both.includes(2) && !Parts::One.includes(2));
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!("42", Answer::from(42_u64).to_string());
+        assert_eq!("a,b", Answer::from("a,b").to_string());
+        assert_eq!(Answer::Text(u64::MAX.to_string()), Answer::from(u64::MAX));
+    }
+}
@@ -0,0 +1,548 @@
+//! A full-screen dashboard for browsing and running days, started with `advent2020 tui`.
+//!
+//! The days run on a background thread so the screen keeps responding, one at a time.
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, Write};
+use std::panic;
+use std::path::PathBuf;
+use std::thread::{self, Scope, ScopedJoinHandle};
+use std::time::{Duration, Instant};
+
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::screen::AlternateScreen;
+use termion::{clear, color, cursor, style};
+
+use crate::bench::fmt_micros;
+use crate::cancel::{panic_message, Cancel};
+use crate::config::Config;
+use crate::log::{self, capture, Level};
+use crate::solver::{Answer, Parts, Puzzle, Registry, Timings};
+
+/// The name given to the threads days run on, so their panics can be kept off the screen.
+const RUNNER: &str = "tui-day";
+
+/// Width of the day list on the left of the screen.
+const LIST_WIDTH: usize = 38;
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    NotRun,
+    Running,
+    Solved,
+    Failed,
+}
+
+/// What we know about one day.
+struct DayView<'a> {
+    puzzle: &'a dyn Puzzle,
+    input: PathBuf,
+    /// How long each run may take.
+    timeout: Option<Duration>,
+    status: Status,
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+    timings: Timings,
+    /// Why the last run failed, over however many lines it takes.
+    error: String,
+    /// Anything the day had to say on its last run.
+    log: String,
+}
+
+/// What the keyboard is doing.
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Browse,
+    /// Typing a new input file for the selected day.
+    EditInput(String),
+}
+
+/// What the event loop should do after a key.
+#[derive(Debug, PartialEq)]
+enum Action {
+    Nothing,
+    Run(Parts),
+    /// Stop the day that's running.
+    Cancel,
+    Quit,
+}
+
+type Outcome = (Result<(Option<Answer>, Option<Answer>, Timings), String>, String);
+
+struct App<'a> {
+    days: Vec<DayView<'a>>,
+    selected: usize,
+    mode: Mode,
+    log_level: Level,
+    /// A one-line message for the bottom of the screen.
+    message: String,
+}
+
+impl<'a> App<'a> {
+    fn new(registry: &'a Registry, config: &Config) -> Self {
+        let days = registry
+            .iter()
+            .map(|puzzle| DayView {
+                puzzle,
+                input: config.data_dir.join(format!("data{}.txt", puzzle.id())),
+                timeout: config.timeout_for(puzzle.id()),
+                status: Status::NotRun,
+                part1: None,
+                part2: None,
+                timings: Timings::default(),
+                error: String::new(),
+                log: String::new(),
+            })
+            .collect();
+        App {
+            days,
+            selected: 0,
+            mode: Mode::Browse,
+            log_level: config.log_level.unwrap_or(Level::Warn),
+            message: String::new(),
+        }
+    }
+
+    fn running(&self) -> bool {
+        self.days.iter().any(|d| d.status == Status::Running)
+    }
+
+    fn handle_key(&mut self, key: Key) -> Action {
+        match &mut self.mode {
+            Mode::EditInput(text) => {
+                match key {
+                    Key::Char('\n') => {
+                        let day = &mut self.days[self.selected];
+                        day.input = PathBuf::from(text.trim());
+                        day.status = Status::NotRun;
+                        self.message = format!("Day {} will read {}", day.puzzle.id(), day.input.display());
+                        self.mode = Mode::Browse;
+                    }
+                    Key::Char(c) => text.push(c),
+                    Key::Backspace => {
+                        text.pop();
+                    }
+                    Key::Esc | Key::Ctrl('c') => self.mode = Mode::Browse,
+                    _ => {}
+                }
+                return Action::Nothing;
+            }
+            Mode::Browse => {}
+        }
+
+        let last = self.days.len().saturating_sub(1);
+        match key {
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => return Action::Quit,
+            Key::Up | Key::Char('k') => self.selected = self.selected.saturating_sub(1),
+            Key::Down | Key::Char('j') => self.selected = (self.selected + 1).min(last),
+            Key::PageUp => self.selected = self.selected.saturating_sub(10),
+            Key::PageDown => self.selected = (self.selected + 10).min(last),
+            Key::Home | Key::Char('g') => self.selected = 0,
+            Key::End | Key::Char('G') => self.selected = last,
+            Key::Char('i') => {
+                let current = self.days[self.selected].input.display().to_string();
+                self.mode = Mode::EditInput(current);
+            }
+            Key::Char('c') if self.running() => return Action::Cancel,
+            Key::Char('v') => {
+                self.log_level = match self.log_level {
+                    Level::Warn => Level::Debug,
+                    Level::Debug => Level::Trace,
+                    Level::Trace => Level::Warn,
+                };
+                self.message = format!("Showing {:?} commentary on the next run", self.log_level);
+            }
+            Key::Char(c @ ('1' | '2' | 'r' | '\n')) => {
+                if self.running() {
+                    self.message = String::from("Still running, one day at a time");
+                    return Action::Nothing;
+                }
+                return Action::Run(match c {
+                    '1' => Parts::One,
+                    '2' => Parts::Two,
+                    _ => Parts::Both,
+                });
+            }
+            _ => {}
+        }
+        Action::Nothing
+    }
+
+    /// Takes in how a run of the selected day went.
+    fn finish(&mut self, index: usize, outcome: Outcome) {
+        let day = &mut self.days[index];
+        let (result, log) = outcome;
+        day.log = log;
+        match result {
+            Ok((part1, part2, timings)) => {
+                // Running one part leaves the other's last answer in place.
+                if part1.is_some() {
+                    day.part1 = part1;
+                    day.timings.part1 = timings.part1;
+                }
+                if part2.is_some() {
+                    day.part2 = part2;
+                    day.timings.part2 = timings.part2;
+                }
+                day.timings.parse = timings.parse;
+                day.error.clear();
+                day.status = Status::Solved;
+                self.message = format!("Day {} solved in {}", day.puzzle.id(), fmt_micros(timings.total()));
+            }
+            Err(err) => {
+                day.error = err;
+                day.status = Status::Failed;
+                self.message = format!("Day {} failed", day.puzzle.id());
+            }
+        }
+    }
+
+    /// The whole screen as lines of text, each already cut to `width` columns.
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let body = height.saturating_sub(2);
+        let detail_width = width.saturating_sub(LIST_WIDTH + 3);
+
+        // Keep the selected day in view on short terminals.
+        let first = (self.selected + 1).saturating_sub(body.saturating_sub(1));
+        let mut list = vec![format!("{:>3}  {:<24}{:>8}", "Day", "Title", "Status")];
+        for (i, day) in self.days.iter().enumerate().skip(first) {
+            let status = match day.status {
+                Status::NotRun => "-",
+                Status::Running => "running",
+                Status::Solved => "ok",
+                Status::Failed => "failed",
+            };
+            let line = format!(
+                "{:>3}  {:<24}{:>8}",
+                day.puzzle.id(),
+                fit(day.puzzle.title(), 24),
+                status
+            );
+            list.push(if i == self.selected {
+                format!("{}{}{}", style::Invert, fit(&line, LIST_WIDTH), style::Reset)
+            } else {
+                line
+            });
+        }
+
+        let detail = self.detail(detail_width);
+
+        let mut lines = vec![fit(
+            &format!(
+                "{}{:<w$}{}",
+                style::Bold,
+                " Advent of Code 2020",
+                style::Reset,
+                w = width
+            ),
+            width,
+        )];
+        for row in 0..body {
+            let left = list.get(row).map_or("", String::as_str);
+            let right = detail.get(row).map_or("", String::as_str);
+            lines.push(format!("{} | {}", pad(left, LIST_WIDTH), fit(right, detail_width)));
+        }
+        lines.push(match &self.mode {
+            Mode::EditInput(text) => fit(&format!("Input file: {}_", text), width),
+            Mode::Browse if !self.message.is_empty() => fit(&self.message, width),
+            Mode::Browse => fit(
+                "[up/down] select  [1] part 1  [2] part 2  [r] both  [i] input  [v] log level  [q] quit",
+                width,
+            ),
+        });
+        lines
+    }
+
+    /// The pane on the right about the selected day.
+    fn detail(&self, width: usize) -> Vec<String> {
+        let day = &self.days[self.selected];
+        let answer = |answer: &Option<Answer>, time| match answer {
+            Some(answer) => format!(
+                "{}{}{}  ({})",
+                color::Fg(color::LightWhite),
+                answer,
+                color::Fg(color::Reset),
+                fmt_micros(time)
+            ),
+            None => String::from("-"),
+        };
+
+        let mut lines = vec![
+            format!(
+                "{}Day {}: {}{}",
+                style::Underline,
+                day.puzzle.id(),
+                day.puzzle.title(),
+                style::Reset
+            ),
+            format!("Input:  {}", day.input.display()),
+            String::new(),
+            format!("Part 1: {}", answer(&day.part1, day.timings.part1)),
+            format!("Part 2: {}", answer(&day.part2, day.timings.part2)),
+        ];
+        if day.status == Status::Solved {
+            lines.push(format!("Parse:  {}", fmt_micros(day.timings.parse)));
+        }
+        if !day.error.is_empty() {
+            lines.push(String::new());
+            lines.extend(
+                day.error
+                    .lines()
+                    .map(|l| format!("{}{}{}", color::Fg(color::Red), l, color::Fg(color::Reset))),
+            );
+        }
+        lines.push(String::new());
+        let title = format!("-- Log ({:?}) ", self.log_level);
+        lines.push(format!("{}{}", title, "-".repeat(width.saturating_sub(title.len()))));
+        lines.extend(day.log.lines().map(String::from));
+        lines
+    }
+}
+
+/// Cuts a line down to `width` characters, not counting escape codes, which are assumed to be reset at the end.
+fn fit(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut shown = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Copy escape sequences whole, up to their final letter.
+            out.push(c);
+            for c in chars.by_ref() {
+                out.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if shown < width {
+            out.push(c);
+            shown += 1;
+        }
+    }
+    out
+}
+
+/// Cuts or pads a line to exactly `width` characters.
+fn pad(text: &str, width: usize) -> String {
+    let visible = fit(text, width);
+    let len = strip(&visible).chars().count();
+    format!("{}{}", visible, " ".repeat(width - len))
+}
+
+fn strip(text: &str) -> String {
+    let mut out = String::new();
+    let mut in_escape = false;
+    for c in text.chars() {
+        match (in_escape, c) {
+            (false, '\u{1b}') => in_escape = true,
+            (false, c) => out.push(c),
+            (true, c) if c.is_ascii_alphabetic() => in_escape = false,
+            (true, _) => {}
+        }
+    }
+    out
+}
+
+/// Reads the input and runs a day, catching every kind of failure as a message for the screen.
+fn solve(puzzle: &dyn Puzzle, input: &PathBuf, parts: Parts, cancel: &Cancel) -> Outcome {
+    capture(|| {
+        let data = fs::read_to_string(input).map_err(|err| format!("Could not read {}: {}", input.display(), err))?;
+        let solved = cancel
+            .run(|| puzzle.solve_timed(&data, parts).map(|(s, t)| (s.part1, s.part2, t)))
+            .map_err(|stopped| format!("Stopped: {}", stopped))?;
+        solved.map_err(|err| err.render())
+    })
+}
+
+/// A day running in the background.
+struct Running<'scope> {
+    index: usize,
+    handle: ScopedJoinHandle<'scope, Outcome>,
+    cancel: Cancel,
+}
+
+fn start<'scope, 'env>(
+    scope: &'scope Scope<'scope, 'env>,
+    index: usize,
+    day: &DayView<'env>,
+    parts: Parts,
+) -> io::Result<Running<'scope>> {
+    let (puzzle, input) = (day.puzzle, day.input.clone());
+    let cancel = Cancel::new(day.timeout);
+    let token = cancel.clone();
+    let handle = thread::Builder::new()
+        .name(String::from(RUNNER))
+        .spawn_scoped(scope, move || solve(puzzle, &input, parts, &token))?;
+    Ok(Running { index, handle, cancel })
+}
+
+/// Runs the dashboard until the user quits.
+pub fn run(registry: &Registry, config: &Config) -> io::Result<()> {
+    let mut app = App::new(registry, config);
+
+    // A panicking day would otherwise scribble its message over the screen. It's shown in the day's pane instead.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(RUNNER) {
+            default_hook(info);
+        }
+    }));
+
+    let result = thread::scope(|scope| -> io::Result<()> {
+        let mut screen = cursor::HideCursor::from(AlternateScreen::from(io::stdout().into_raw_mode()?));
+        let mut keys = termion::async_stdin().keys();
+        let mut running: Option<Running> = None;
+        let mut dirty = true;
+        let mut last_draw = Instant::now();
+
+        loop {
+            if let Some(Ok(key)) = keys.next() {
+                dirty = true;
+                match app.handle_key(key) {
+                    Action::Quit => break,
+                    Action::Run(parts) => {
+                        let index = app.selected;
+                        log::set_max_level(Some(app.log_level));
+                        running = Some(start(scope, index, &app.days[index], parts)?);
+                        let day = &mut app.days[index];
+                        day.status = Status::Running;
+                        app.message = format!("Running day {}... ([c] to cancel)", day.puzzle.id());
+                    }
+                    Action::Cancel => {
+                        if let Some(run) = &running {
+                            run.cancel.cancel();
+                        }
+                    }
+                    Action::Nothing => {}
+                }
+                // Deal with every waiting key before drawing.
+                continue;
+            }
+
+            if running.as_ref().is_some_and(|run| run.handle.is_finished()) {
+                let Running { index, handle, .. } = running.take().unwrap();
+                let outcome = handle
+                    .join()
+                    .unwrap_or_else(|payload| (Err(format!("panicked: {}", panic_message(&*payload))), String::new()));
+                app.finish(index, outcome);
+                dirty = true;
+            }
+
+            if dirty || last_draw.elapsed() > Duration::from_secs(1) {
+                let (width, height) = termion::terminal_size()?;
+                write!(screen, "{}", clear::All)?;
+                for (row, line) in app.render(width.into(), height.into()).iter().enumerate() {
+                    write!(
+                        screen,
+                        "{}{}{}",
+                        cursor::Goto(1, u16::try_from(row + 1).unwrap_or(u16::MAX)),
+                        line,
+                        style::Reset
+                    )?;
+                }
+                screen.flush()?;
+                dirty = false;
+                last_draw = Instant::now();
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        if let Some(run) = &running {
+            // Days that check for cancellation stop straight away, but the rest have to be waited for.
+            run.cancel.cancel();
+            write!(
+                screen,
+                "{}{}Waiting for day {} to finish...",
+                clear::All,
+                cursor::Goto(1, 1),
+                app.days[run.index].puzzle.id()
+            )?;
+            screen.flush()?;
+        }
+        Ok(())
+    });
+
+    let _ = panic::take_hook();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_keys() {
+        let registry = days::registry();
+        let config = Config::new(["advent2020", "tui"].iter().map(|s| s.to_string())).unwrap();
+        let mut app = App::new(&registry, &config);
+        assert_eq!(PathBuf::from("data/data1.txt"), app.days[0].input);
+
+        assert_eq!(Action::Nothing, app.handle_key(Key::Up));
+        assert_eq!(0, app.selected);
+        app.handle_key(Key::Down);
+        app.handle_key(Key::Char('j'));
+        assert_eq!(2, app.selected);
+        app.handle_key(Key::End);
+        assert_eq!(registry.len() - 1, app.selected);
+        app.handle_key(Key::Home);
+
+        app.handle_key(Key::Char('i'));
+        assert_eq!(Mode::EditInput(String::from("data/data1.txt")), app.mode);
+        for _ in 0.."data1.txt".len() {
+            app.handle_key(Key::Backspace);
+        }
+        for c in "mine.txt".chars() {
+            // Typing a 'q' here mustn't quit.
+            assert_eq!(Action::Nothing, app.handle_key(Key::Char(c)));
+        }
+        app.handle_key(Key::Char('\n'));
+        assert_eq!(Mode::Browse, app.mode);
+        assert_eq!(PathBuf::from("data/mine.txt"), app.days[0].input);
+
+        assert_eq!(Action::Run(Parts::Two), app.handle_key(Key::Char('2')));
+        app.days[0].status = Status::Running;
+        assert_eq!(Action::Nothing, app.handle_key(Key::Char('r')));
+        assert_eq!(Action::Quit, app.handle_key(Key::Char('q')));
+    }
+
+    #[test]
+    fn test_run() {
+        let registry = days::registry();
+        let config = Config::new(["advent2020", "tui"].iter().map(|s| s.to_string())).unwrap();
+        let mut app = App::new(&registry, &config);
+
+        let puzzle = app.days[0].puzzle;
+        let cancel = Cancel::new(None);
+        app.finish(0, solve(puzzle, &PathBuf::from("data/data1.txt"), Parts::One, &cancel));
+        assert_eq!(Status::Solved, app.days[0].status);
+        assert!(app.days[0].part1.is_some());
+        assert_eq!(None, app.days[0].part2);
+
+        app.finish(0, solve(puzzle, &PathBuf::from("data/nope.txt"), Parts::Both, &cancel));
+        assert_eq!(Status::Failed, app.days[0].status);
+        assert!(app.days[0].error.starts_with("Could not read data/nope.txt"));
+        // The last good answer is kept.
+        assert!(app.days[0].part1.is_some());
+
+        // Day 15 checks for cancellation as soon as it starts.
+        cancel.cancel();
+        let puzzle = app.days[14].puzzle;
+        app.finish(14, solve(puzzle, &PathBuf::from("data/data15.txt"), Parts::Both, &cancel));
+        assert_eq!("Stopped: cancelled", app.days[14].error);
+
+        let screen = app.render(100, 30);
+        assert_eq!(30, screen.len());
+        assert!(screen.iter().all(|line| strip(line).chars().count() <= 100));
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!("abc", fit("abcdef", 3));
+        let red = format!("{}abcdef{}", color::Fg(color::Red), color::Fg(color::Reset));
+        assert_eq!("abc", strip(&fit(&red, 3)));
+        assert_eq!("ab   ", pad("ab", 5));
+    }
+}